@@ -384,6 +384,16 @@ function createTilePicker() {
     wildBtn.textContent = 'W';
     wildBtn.addEventListener('click', () => addTileToHand('w'));
     wildElement.appendChild(wildBtn);
+
+    // Twist jokers
+    ['wd', 'wc', 'wm'].forEach(code => {
+        const btn = document.createElement('button');
+        btn.className = 'tile-btn wild-btn';
+        btn.dataset.tile = code;
+        btn.textContent = code.toUpperCase();
+        btn.addEventListener('click', () => addTileToHand(code));
+        wildElement.appendChild(btn);
+    });
 }
 
 // Add tile to hand
@@ -490,16 +500,22 @@ function updateHandDisplay() {
     display.appendChild(tilesDiv);
 }
 
+// Check if a tile string is a joker: "w", a numbered joker ("w1"), a color-restricted
+// joker ("wr") or a Twist joker ("wd" duplicate, "wc" color change, "wm" mirror)
+function isJokerTile(tile) {
+    return /^w([1-9]|1[0-5]|[rbykdcm])?$/.test(tile);
+}
+
 // Get tile color from tile string
 function getTileColor(tile) {
-    if (tile === 'w') return 'wild';
+    if (isJokerTile(tile)) return 'wild';
     const colorMap = { 'r': 'red', 'b': 'blue', 'y': 'yellow', 'k': 'black' };
     return colorMap[tile[0]] || 'black';
 }
 
 // Format tile for display
 function formatTileDisplay(tile) {
-    if (tile === 'w') return 'W';
+    if (isJokerTile(tile)) return tile.toUpperCase();
     const colorMap = { 'r': 'R', 'b': 'B', 'y': 'Y', 'k': 'K' };
     return `${colorMap[tile[0]]}${tile.substring(1)}`;
 }
@@ -514,14 +530,14 @@ function parseGroupTiles(input) {
 
     // Check if first part is color+number format (e.g., "b5")
     const firstPart = parts[0];
-    const isColorNumberFormat = /^[rbykw]\d*$/.test(firstPart) || firstPart === 'w';
+    const isColorNumberFormat = /^[rbykw]\d*$/.test(firstPart) || isJokerTile(firstPart);
 
     if (isColorNumberFormat) {
         // Format: "b5 r8 k5" or "b5 r8 w"
         const tiles = [];
         for (const part of parts) {
-            if (part === 'w') {
-                tiles.push('w');
+            if (isJokerTile(part)) {
+                tiles.push(part);
             } else {
                 const match = part.match(/^([rbyk])(\d{1,2})$/);
                 if (!match) {
@@ -554,11 +570,11 @@ function parseGroupTiles(input) {
         // Validate colors and create tiles
         const tiles = [];
         for (const color of colors) {
-            if (!['r', 'b', 'y', 'k', 'w'].includes(color)) {
+            if (!['r', 'b', 'y', 'k'].includes(color) && !isJokerTile(color)) {
                 throw new Error(`Invalid color: ${color}. Use r, b, y, k, or w.`);
             }
-            if (color === 'w') {
-                tiles.push('w');
+            if (isJokerTile(color)) {
+                tiles.push(color);
             } else {
                 tiles.push(`${color}${numVal}`);
             }
@@ -578,14 +594,14 @@ function parseRunTiles(input) {
 
     // Check if first part is color+number format (e.g., "y6")
     const firstPart = parts[0];
-    const isColorNumberFormat = /^[rbykw]\d+$/.test(firstPart) || firstPart === 'w';
+    const isColorNumberFormat = /^[rbykw]\d+$/.test(firstPart) || isJokerTile(firstPart);
 
     if (isColorNumberFormat) {
         // Format: "y6 y7 y8" or "y6 y7 w"
         const tiles = [];
         for (const part of parts) {
-            if (part === 'w') {
-                tiles.push('w');
+            if (isJokerTile(part)) {
+                tiles.push(part);
             } else {
                 const match = part.match(/^([rbyk])(\d{1,2})$/);
                 if (!match) {
@@ -617,8 +633,8 @@ function parseRunTiles(input) {
         // Validate numbers and create tiles
         const tiles = [];
         for (const numStr of numbers) {
-            if (numStr === 'w') {
-                tiles.push('w');
+            if (isJokerTile(numStr)) {
+                tiles.push(numStr);
             } else {
                 const num = parseInt(numStr);
                 if (isNaN(num) || num < 1 || num > 13) {
@@ -680,7 +696,7 @@ function addMeldToTable() {
 
 // Validate tile string
 function isValidTile(tile) {
-    if (tile === 'w') return true;
+    if (isJokerTile(tile)) return true;
     const match = tile.match(/^([rbyk])(\d{1,2})$/);
     if (!match) return false;
     const num = parseInt(match[2]);
@@ -831,6 +847,7 @@ async function solve() {
 
     const strategy = document.getElementById('strategy').value;
     const timeLimit = parseInt(document.getElementById('time-limit').value);
+    const houseRules = getHouseRules();
 
     const solveBtn = document.getElementById('solve-btn');
     solveBtn.disabled = true;
@@ -863,11 +880,22 @@ async function solve() {
             handArray,
             table,
             strategy,
-            timeLimit
+            timeLimit,
+            houseRules
         }
     });
 }
 
+// Read the house rules from the solve controls
+function getHouseRules() {
+    return {
+        replay_with_hand_tile: document.getElementById('rule-replay-with-hand-tile').checked,
+        no_retrieval_from_groups_of_three: document.getElementById('rule-no-retrieval-groups-of-three').checked,
+        replacement_from_hand_only: document.getElementById('rule-replacement-from-hand-only').checked,
+        wrap_around_runs: document.getElementById('rule-wrap-around-runs').checked
+    };
+}
+
 function showSolverResultToast(result, timeLimit) {
     const completionReason = result.search_completed ? 'Search Complete' : 'Timeout';
 
@@ -884,6 +912,7 @@ function showSolverResultToast(result, timeLimit) {
             <strong>${completionReason}</strong><br>
             Depth: ${result.depth_reached}<br>
             ${improvementText}<br>
+            ${heldBackText(result.held_back)}
             Time: ${timeLimit}ms
         `;
         type = 'success';
@@ -900,6 +929,18 @@ function showSolverResultToast(result, timeLimit) {
     showToast(title, message, type, 5000);
 }
 
+// Summarize the tiles the keep-options strategy held back, grouped by reason
+function heldBackText(heldBack) {
+    if (!heldBack || heldBack.length === 0) return '';
+    const labels = { reserve_meld: 'meld for next turn', wild: 'joker', pair: 'pair', near_run: 'near run' };
+    const byReason = {};
+    heldBack.forEach(({ tile, reason }) => {
+        (byReason[reason] = byReason[reason] || []).push(tile);
+    });
+    const parts = Object.entries(byReason).map(([reason, tiles]) => `${labels[reason] || reason}: ${tiles.join(' ')}`);
+    return `Held back: ${parts.join('; ')}<br>`;
+}
+
 // Render tiles as HTML with consistent styling
 function renderTilesAsHtml(tiles) {
     return tiles.map(tile => {
@@ -909,10 +950,23 @@ function renderTilesAsHtml(tiles) {
     }).join('');
 }
 
+// Describe a tile in words, e.g. "red 7"
+function describeTile(tile) {
+    const colorNames = { 'r': 'red', 'b': 'blue', 'y': 'yellow', 'k': 'black' };
+    return `${colorNames[tile[0]] || tile[0]} ${tile.substring(1)}`;
+}
+
+// Render the tiles the jokers in a meld are played as, e.g. "(joker as red 7)"
+function renderJokersAsHtml(meld) {
+    if (!meld.jokers || meld.jokers.length === 0) return '';
+    const notes = meld.jokers.map(j => `joker as ${describeTile(j.represents)}`).join(', ');
+    return ` <span class="joker-note">(${notes})</span>`;
+}
+
 // Render a meld as HTML
 function renderMeldAsHtml(meld) {
     const tilesHtml = renderTilesAsHtml(meld.tiles);
-    return `<span class="meld-type-badge">${meld.type}</span> <span class="meld-tiles">${tilesHtml}</span>`;
+    return `<span class="meld-type-badge">${meld.type}</span> <span class="meld-tiles">${tilesHtml}</span>${renderJokersAsHtml(meld)}`;
 }
 
 // Render a human-readable move as HTML
@@ -948,6 +1002,21 @@ function renderHumanMoveAsHtml(move) {
             return `Swap ${swapsHtml} in ${renderMeldAsHtml(move.original)} → ${renderMeldAsHtml(move.result)}`;
         }
 
+        case 'move_tile': {
+            const tileHtml = `<span class="meld-tiles">${renderTilesAsHtml([move.tile])}</span>`;
+            const addedHtml = move.added_tiles.length > 0
+                ? ` with <span class="meld-tiles">${renderTilesAsHtml(move.added_tiles)}</span> from hand`
+                : '';
+            const target = move.to ? `onto ${renderMeldAsHtml(move.to)}` : 'to a new meld';
+            return `Move ${tileHtml} from ${renderMeldAsHtml(move.from)} (leaving ${renderMeldAsHtml(move.remaining)}) ${target}${addedHtml} → ${renderMeldAsHtml(move.result)}`;
+        }
+
+        case 'insert_and_split': {
+            const insertedHtml = renderTilesAsHtml(move.inserted);
+            const partsHtml = move.parts.map(renderMeldAsHtml).join(' and ');
+            return `Split ${renderMeldAsHtml(move.original)} inserting <span class="meld-tiles">${insertedHtml}</span> → ${partsHtml}`;
+        }
+
         case 'rearrange': {
             const consumedHtml = move.consumed.length > 0
                 ? move.consumed.map(renderMeldAsHtml).join(', ')
@@ -964,6 +1033,27 @@ function renderHumanMoveAsHtml(move) {
     }
 }
 
+// Name a meld slot from the step-by-step plan: table melds first, then new ones
+function describeSlot(slot, tableSize) {
+    return slot < tableSize ? `meld #${slot + 1}` : `new meld ${String.fromCharCode(65 + slot - tableSize)}`;
+}
+
+// Render one physical step of the plan
+function renderStepAsHtml(step, tableSize) {
+    switch (step.type) {
+        case 'split':
+            return `Split ${describeSlot(step.slot, tableSize)} after its first ${step.after} tiles; the rest becomes ${describeSlot(step.new_slot, tableSize)}`;
+        case 'move_tile':
+            return `Move ${renderTilesAsHtml([step.tile])} from ${describeSlot(step.from, tableSize)} to ${describeSlot(step.to, tableSize)}`;
+        case 'place_from_rack':
+            return `Place ${renderTilesAsHtml([step.tile])} from your rack into ${describeSlot(step.to, tableSize)}`;
+        case 'take_to_rack':
+            return `Take ${renderTilesAsHtml([step.tile])} from ${describeSlot(step.from, tableSize)} back to your rack`;
+        default:
+            return `Unknown step: ${step.type}`;
+    }
+}
+
 // Display solver results
 function displayResults(result) {
     const section = document.getElementById('results-section');
@@ -972,9 +1062,13 @@ function displayResults(result) {
     section.style.display = 'block';
 
     if (!result.success) {
+        const nearPlays = (result.near_plays || []).slice(0, 3).map(play =>
+            `<div>One tile short of ${renderTilesAsHtml(play.meld.tiles)} (needs ${describeTile(play.missing)})</div>`
+        ).join('');
         display.innerHTML = `
             <div class="result-error">
                 ${result.error || 'No solution found'}
+                ${nearPlays}
             </div>
         `;
         return;
@@ -1010,6 +1104,23 @@ function displayResults(result) {
         html += '</ol>';
     }
 
+    // Add collapsible step-by-step tile moves
+    const steps = result.steps || [];
+    if (steps.length > 0) {
+        html += `
+            <details class="raw-moves-section">
+                <summary>Show step-by-step tile moves (${steps.length})</summary>
+                <ol class="move-list steps">
+        `;
+        steps.forEach((step, index) => {
+            html += `<li class="move-item">`;
+            html += `<span class="move-number">${index + 1}.</span>`;
+            html += renderStepAsHtml(step, table.length);
+            html += `</li>`;
+        });
+        html += '</ol></details>';
+    }
+
     // Add collapsible raw moves section for debugging
     if (rawMoves.length > 0) {
         html += `
@@ -1033,8 +1144,7 @@ function displayResults(result) {
                 }
             } else if (move.action === 'laydown') {
                 const meld = move.meld;
-                const tilesHtml = renderTilesAsHtml(meld.tiles);
-                html += `Lay down <span class="meld-type-badge">${meld.type}</span> <span class="meld-tiles">${tilesHtml}</span>`;
+                html += `Lay down ${renderMeldAsHtml(meld)}`;
             }
 
             html += `</li>`;
@@ -1652,6 +1762,7 @@ function updateHandFromImage(result, processingId, modeLabel) {
         updateHandDisplay();
         updateTileCounts();
         showToast('Hand Updated', `Imported ${result.tiles.length} tiles from image`, 'success', 5000);
+        warnAboutTileExcess();
         removeFromProcessingQueue(processingId);
     } catch (error) {
         showToast(`${modeLabel} Processing Failed`, `Error updating hand: ${error.message}`, 'error', 8000);
@@ -1724,6 +1835,7 @@ function updateTableFromImage(result, processingId, modeLabel) {
         } else if (duplicateCount > 0) {
             showToast('Duplicates Detected', `${duplicateCount} duplicate meld(s) need confirmation`, 'info', 5000);
         }
+        warnAboutTileExcess();
 
         removeFromProcessingQueue(processingId);
     } catch (error) {
@@ -1733,6 +1845,37 @@ function updateTableFromImage(result, processingId, modeLabel) {
     }
 }
 
+// Warn when hand and table hold more copies of a tile than the tile set has,
+// which usually means a photo was misread
+function warnAboutTileExcess() {
+    if (!wasmModule || !wasmModule.check_tile_conservation) return;
+
+    const handArray = [];
+    hand.forEach((count, tile) => {
+        for (let i = 0; i < count; i++) {
+            handArray.push(tile);
+        }
+    });
+
+    // Standard 106-tile set
+    const result = JSON.parse(wasmModule.check_tile_conservation(
+        JSON.stringify(handArray),
+        JSON.stringify(table),
+        '{}'
+    ));
+
+    if (!result.success) {
+        console.warn('Tile conservation check failed:', result.error);
+        return;
+    }
+    if (result.excess.length > 0) {
+        const listed = result.excess
+            .map(e => `${formatTileDisplay(e.tile)} (${e.found} of ${e.available})`)
+            .join(', ');
+        showToast('Check Tiles', `More copies than the tile set holds: ${listed}`, 'error', 8000);
+    }
+}
+
 // Clear hand
 function clearHand() {
     hand.clear();
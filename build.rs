@@ -3,7 +3,7 @@ use std::process::Command;
 fn main() {
     // Capture the first 8 characters of the current git commit hash
     let output = Command::new("git")
-        .args(&["rev-parse", "--short=8", "HEAD"])
+        .args(["rev-parse", "--short=8", "HEAD"])
         .output();
 
     let commit_hash = match output {
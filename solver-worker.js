@@ -63,14 +63,17 @@ self.onmessage = function(e) {
         }
 
         try {
-            const { handArray, table, strategy, timeLimit } = data;
+            const { handArray, table, strategy, timeLimit, houseRules, constraints, tileSet } = data;
 
             // Call WASM solver
-            const resultJson = wasmModule.solve_rummikub(
+            const resultJson = wasmModule.solve_rummikub_with_constraints(
                 JSON.stringify(handArray),
                 JSON.stringify(table),
                 strategy,
-                BigInt(timeLimit)
+                BigInt(timeLimit),
+                JSON.stringify(houseRules || {}),
+                JSON.stringify(constraints || {}),
+                JSON.stringify(tileSet || {})
             );
 
             const result = JSON.parse(resultJson);
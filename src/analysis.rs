@@ -0,0 +1,1577 @@
+//! Analyses built on the solver: what the unseen tiles would do for a hand,
+//! whether to play now or draw, how to spread plays over several turns,
+//! which near-melds the hand is waiting on, which tiles can be played,
+//! whether to play a joker now or hold it, graded hints toward the best
+//! play, and what-if comparisons against hypothetical positions.
+
+use crate::solver::{self, HouseRules, HumanMove, ScoringStrategy, SolverMove, SolverResult, TimeTracker};
+use crate::{Hand, Meld, MeldType, Table, Tile, TileSet};
+use std::collections::HashMap;
+
+/// What drawing one kind of unseen tile would let the player do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrawOutcome {
+    /// The tile drawn (`Tile::wild()` for a joker)
+    pub tile: Tile,
+    /// Unseen copies of the tile
+    pub unseen: u8,
+    /// The best play with the tile in hand lays the drawn tile down
+    pub can_play: bool,
+    /// Hand tiles the best play with the tile in hand lays down, counting the
+    /// drawn tile if it is played
+    pub tiles_played: usize,
+    /// The best play empties the hand
+    pub goes_out: bool,
+}
+
+/// The outlook for the next draw, over every unseen tile kind
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawOutlook {
+    /// One outcome per unseen tile kind, in tile order
+    pub outcomes: Vec<DrawOutcome>,
+    /// Hand tiles the best play lays down without drawing
+    pub baseline_played: usize,
+    /// Chance the draw lets the player lay down more tiles than now
+    pub useful_probability: f64,
+    /// Chance the draw lets the player go out
+    pub out_probability: f64,
+    /// Every solver run finished its search within its share of the time
+    pub search_completed: bool,
+}
+
+/// Work out what each unseen tile kind would let the player do if drawn.
+///
+/// The solver runs once on the hand as it is and once per unseen tile kind
+/// with that tile added, each with an equal share of `max_ms`. A drawn tile
+/// only counts as playable when the best play lays it down, so a hand that
+/// can already play does not make every tile look useful. Probabilities
+/// weigh each kind by its unseen copies, treating every unseen tile as equally
+/// likely to be drawn.
+pub fn draw_outlook(
+    table: &Table,
+    hand: &Hand,
+    unseen: &Hand,
+    max_ms: u64,
+    strategy: ScoringStrategy,
+    rules: &HouseRules,
+) -> DrawOutlook {
+    let kinds: Vec<(Tile, u8)> = unseen.iter().map(|(t, &c)| (*t, c)).filter(|(_, c)| *c > 0).collect();
+    let share_ms = (max_ms / (kinds.len() as u64 + 1)).max(1);
+
+    let baseline = solve(table, hand, share_ms, strategy, rules);
+    let mut search_completed = baseline.search_completed;
+    let baseline_played = solver::tiles_played(table, baseline.moves.as_deref().unwrap_or_default());
+    let hand_size = hand.len();
+
+    let mut outcomes = Vec::with_capacity(kinds.len());
+    for (tile, copies) in kinds {
+        let mut drawn = hand.clone();
+        drawn.add(tile);
+        let result = solve(table, &drawn, share_ms, strategy, rules);
+        search_completed &= result.search_completed;
+        let moves = result.moves.unwrap_or_default();
+        let tiles_played = solver::tiles_played(table, &moves);
+
+        // The drawn tile is played if the rack ends with fewer copies of it
+        let (mut after_table, mut after_hand) = (table.clone(), drawn.clone());
+        solver::apply_moves(&mut after_table, &mut after_hand, &moves);
+
+        outcomes.push(DrawOutcome {
+            tile,
+            unseen: copies,
+            can_play: after_hand.count(&tile) < drawn.count(&tile),
+            tiles_played,
+            goes_out: tiles_played == hand_size + 1,
+        });
+    }
+
+    let total: usize = outcomes.iter().map(|o| o.unseen as usize).sum();
+    let share = |pick: &dyn Fn(&DrawOutcome) -> bool| {
+        if total == 0 {
+            return 0.0;
+        }
+        let copies: usize = outcomes.iter().filter(|o| pick(o)).map(|o| o.unseen as usize).sum();
+        copies as f64 / total as f64
+    };
+    let useful_probability = share(&|o| o.tiles_played > baseline_played);
+    let out_probability = share(&|o| o.goes_out);
+
+    DrawOutlook {
+        outcomes,
+        baseline_played,
+        useful_probability,
+        out_probability,
+        search_completed,
+    }
+}
+
+/// The two choices a player has on their turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnChoice {
+    /// Lay down the solver's best play
+    Play,
+    /// Play nothing and draw a tile
+    Draw,
+}
+
+/// Most own turns the advisors look ahead
+pub const MAX_ADVISOR_TURNS: usize = 6;
+
+/// Most draw sequences the advisors simulate for each choice
+pub const MAX_ADVISOR_SAMPLES: usize = 500;
+
+/// Settings for the draw-or-play advisor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdvisorSettings {
+    /// Own turns to look ahead, the current one included
+    /// (up to `MAX_ADVISOR_TURNS`)
+    pub turns: usize,
+    /// Random draw sequences simulated for each choice
+    /// (up to `MAX_ADVISOR_SAMPLES`)
+    pub samples: usize,
+    /// Time limit for the whole advice, shared between the solver runs
+    pub max_ms: u64,
+    /// Seed for the draw sampling, so that advice is repeatable
+    pub seed: u64,
+}
+
+impl Default for AdvisorSettings {
+    fn default() -> Self {
+        AdvisorSettings {
+            turns: 3,
+            samples: 20,
+            max_ms: 2000,
+            seed: 0x5eed,
+        }
+    }
+}
+
+/// The advisor's verdict on playing now versus drawing
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnAdvice {
+    pub recommendation: TurnChoice,
+    /// The best play available now, if any
+    pub best_play: Option<Vec<SolverMove>>,
+    /// Expected tiles left in hand after the lookahead when playing now,
+    /// or None if there is no play
+    pub expected_remaining_if_play: Option<f64>,
+    /// Expected tiles left in hand after the lookahead when drawing now
+    pub expected_remaining_if_draw: f64,
+    /// Samples simulated for each choice before the time ran out
+    pub samples_completed: usize,
+}
+
+/// Compare playing the best move now against drawing.
+///
+/// Each choice is followed by `settings.turns - 1` more own turns, simulated
+/// `settings.samples` times with draws taken at random from `unseen`. On those
+/// later turns the player lays down the best play if there is one and draws
+/// otherwise. The table is assumed to change only through the player's own
+/// plays. Playing is recommended unless drawing leaves fewer tiles expected.
+///
+/// Every solver run gets an equal share of `settings.max_ms`. Once the time
+/// is up no further samples are started, though at least one always is; the
+/// expectations average the samples completed.
+pub fn advise_draw_or_play(
+    table: &Table,
+    hand: &Hand,
+    unseen: &Hand,
+    strategy: ScoringStrategy,
+    rules: &HouseRules,
+    settings: &AdvisorSettings,
+) -> TurnAdvice {
+    // The best play now, then the later turns of every sample for both choices
+    let lookahead = Lookahead::new(settings, 1);
+    let best_play = solve(table, hand, lookahead.solve_ms, strategy, rules).moves.filter(|moves| !moves.is_empty());
+
+    let (expected_remaining_if_play, expected_remaining_if_draw, samples_completed) = match &best_play {
+        Some(moves) => {
+            let (expected, completed) =
+                lookahead.expected_remaining(table, hand, unseen, &[Some(moves), None], strategy, rules);
+            (Some(expected[0]), expected[1], completed)
+        }
+        None => {
+            let (expected, completed) = lookahead.expected_remaining(table, hand, unseen, &[None], strategy, rules);
+            (None, expected[0], completed)
+        }
+    };
+    let recommendation = match expected_remaining_if_play {
+        Some(play) if play <= expected_remaining_if_draw => TurnChoice::Play,
+        _ => TurnChoice::Draw,
+    };
+
+    TurnAdvice {
+        recommendation,
+        best_play,
+        expected_remaining_if_play,
+        expected_remaining_if_draw,
+        samples_completed,
+    }
+}
+
+/// One turn of a multi-turn plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedTurn {
+    pub choice: TurnChoice,
+    /// Moves for the table as it stands at this turn (empty when drawing)
+    pub moves: Vec<SolverMove>,
+}
+
+/// A plan for the player's next turns
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnPlan {
+    /// One entry per turn, ending early if the plan goes out
+    pub turns: Vec<PlannedTurn>,
+    /// Tiles expected in hand after the last turn, drawn tiles included
+    pub final_rack: usize,
+    /// Every solver run finished and the deadline cut no line short
+    pub search_completed: bool,
+}
+
+/// Most own turns a plan covers
+pub const MAX_PLAN_TURNS: usize = 6;
+
+/// Largest best play whose melds are also tried in part
+const MAX_SPLIT_MELDS: usize = 4;
+
+/// Most plays expanded at each turn of a plan, besides drawing
+const MAX_PLAN_CANDIDATES: usize = 6;
+
+/// Plan the player's next `turns` turns (at most `MAX_PLAN_TURNS`, fewer are
+/// planned if more are asked for) to end with the smallest rack.
+///
+/// Drawn tiles are unknown, so the plan counts each draw as a tile that stays
+/// in the rack. Every turn the solver's best play is the move generator: the
+/// plan may lay it down, lay down only some of its melds when it uses hand
+/// tiles alone (keeping the rest for a later turn instead of having to draw),
+/// or draw. Holding everything and going out in a single later turn is one of
+/// the lines considered. Among equal racks the plan that plays the most tiles
+/// first wins.
+///
+/// The whole search shares `max_ms`: each solver run gets an equal part of
+/// the time left for the turns still to plan, and once the time is up any
+/// position not yet solved is planned as a draw. At most `MAX_PLAN_CANDIDATES` plays
+/// are expanded per turn: the best play, each of its melds alone, then the
+/// larger sets of its melds first.
+pub fn plan_turns(
+    table: &Table,
+    hand: &Hand,
+    turns: usize,
+    max_ms: u64,
+    strategy: ScoringStrategy,
+    rules: &HouseRules,
+) -> TurnPlan {
+    let mut search = PlanSearch {
+        timer: TimeTracker::new(max_ms),
+        strategy,
+        rules,
+        completed: true,
+        solved: HashMap::new(),
+    };
+    let (final_rack, turns) = search.plan_from(table, hand, 0, turns.min(MAX_PLAN_TURNS));
+    TurnPlan {
+        turns,
+        final_rack,
+        search_completed: search.completed,
+    }
+}
+
+/// State shared by every branch of a turn plan search
+struct PlanSearch<'a> {
+    timer: TimeTracker,
+    strategy: ScoringStrategy,
+    rules: &'a HouseRules,
+    completed: bool,
+    /// Candidate plays of the positions solved so far. Drawing leaves the
+    /// position as it was and plays can be reached in either order, so the
+    /// same position comes up on many lines.
+    solved: HashMap<(Table, Hand), Vec<Vec<SolverMove>>>,
+}
+
+impl PlanSearch<'_> {
+    /// Best plan from a position with `draws` unknown tiles already drawn.
+    /// Returns the final rack and the turns.
+    fn plan_from(
+        &mut self,
+        table: &Table,
+        hand: &Hand,
+        draws: usize,
+        turns_left: usize,
+    ) -> (usize, Vec<PlannedTurn>) {
+        let rack = hand.len() + draws;
+        if turns_left == 0 || rack == 0 {
+            return (rack, Vec::new());
+        }
+
+        // Drawing is always possible
+        let (draw_rack, draw_turns) = self.plan_from(table, hand, draws + 1, turns_left - 1);
+        let mut best = (draw_rack, PlannedTurn { choice: TurnChoice::Draw, moves: Vec::new() }, draw_turns);
+
+        for moves in self.candidate_plays(table, hand, turns_left) {
+            let (mut next_table, mut next_hand) = (table.clone(), hand.clone());
+            solver::apply_moves(&mut next_table, &mut next_hand, &moves);
+            let (rack, turns) = self.plan_from(&next_table, &next_hand, draws, turns_left - 1);
+
+            let played = solver::tiles_played(table, &moves);
+            let best_played = solver::tiles_played(table, &best.1.moves);
+            if rack < best.0 || (rack == best.0 && played > best_played) {
+                best = (rack, PlannedTurn { choice: TurnChoice::Play, moves }, turns);
+            }
+        }
+
+        let (rack, first, mut rest) = best;
+        rest.insert(0, first);
+        (rack, rest)
+    }
+
+    /// The plays worth planning with: the solver's best play, and when it only
+    /// lays down hand tiles, smaller sets of its melds. Single melds come first
+    /// so that the cap never drops the plays a plan needs to spread its melds
+    /// one per turn; larger sets follow, largest first. Returns nothing for an
+    /// unsolved position once the deadline has passed.
+    fn candidate_plays(&mut self, table: &Table, hand: &Hand, turns_left: usize) -> Vec<Vec<SolverMove>> {
+        let key = (table.clone(), hand.clone());
+        if let Some(plays) = self.solved.get(&key) {
+            return plays.clone();
+        }
+        if self.timer.is_expired() {
+            self.completed = false;
+            return Vec::new();
+        }
+        let solve_ms = (self.timer.remaining_ms() / turns_left as u64).max(1);
+        let mut table_copy = table.clone();
+        let mut hand_copy = hand.clone();
+        let result =
+            solver::find_best_moves_with_rules(&mut table_copy, &mut hand_copy, solve_ms, self.strategy, self.rules);
+        self.completed &= result.search_completed;
+        let Some(best) = result.moves.filter(|moves| !moves.is_empty()) else {
+            self.solved.insert(key, Vec::new());
+            return Vec::new();
+        };
+
+        let direct = best.iter().all(|m| matches!(m, SolverMove::LayDown(_)));
+        let mut subsets: Vec<Vec<SolverMove>> = Vec::new();
+        if direct && best.len() > 1 && best.len() <= MAX_SPLIT_MELDS {
+            for mask in 1..(1u32 << best.len()) - 1 {
+                subsets.push(
+                    best.iter()
+                        .enumerate()
+                        .filter(|(i, _)| mask & (1 << i) != 0)
+                        .map(|(_, m)| m.clone())
+                        .collect(),
+                );
+            }
+        }
+        subsets.sort_by_key(|play| (play.len() > 1, std::cmp::Reverse(solver::tiles_played(table, play))));
+
+        let mut plays = vec![best];
+        plays.extend(subsets);
+        plays.truncate(MAX_PLAN_CANDIDATES);
+        self.solved.insert(key, plays.clone());
+        plays
+    }
+}
+
+/// Play out the given number of turns: lay down the best play if there is
+/// one, draw from the pool otherwise. Returns the tiles left in hand.
+#[allow(clippy::too_many_arguments)]
+fn simulate(
+    table: &mut Table,
+    hand: &mut Hand,
+    pool: &mut Hand,
+    turns: usize,
+    strategy: ScoringStrategy,
+    rules: &HouseRules,
+    solve_ms: u64,
+    rng: &mut SampleRng,
+) -> usize {
+    for _ in 0..turns {
+        if hand.is_empty() {
+            break;
+        }
+        match solve(table, hand, solve_ms, strategy, rules).moves {
+            Some(moves) if !moves.is_empty() => solver::apply_moves(table, hand, &moves),
+            _ => {
+                if let Some(tile) = rng.draw(pool) {
+                    hand.add(tile);
+                }
+            }
+        }
+    }
+    hand.len()
+}
+
+/// The shape of a near-meld: two hand tiles one tile short of a meld
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NearMeldKind {
+    /// Same number in two colors, waiting on a third color
+    Pair,
+    /// Two consecutive numbers of one color, waiting on either end
+    TwoTileRun,
+    /// Two numbers of one color with one missing between them
+    GappedRun,
+}
+
+/// A tile that would complete a near-meld, and how many copies are unseen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Completion {
+    pub tile: Tile,
+    pub unseen: u8,
+}
+
+/// Two hand tiles that one more tile would turn into a meld
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearMeld {
+    pub kind: NearMeldKind,
+    /// The hand tiles, in run or color order
+    pub tiles: Vec<Tile>,
+    /// Tiles that would complete the meld, in tile order
+    pub completing: Vec<Completion>,
+}
+
+impl NearMeld {
+    /// Unseen copies of all the completing tiles together
+    pub fn outs(&self) -> u32 {
+        self.completing.iter().map(|c| c.unseen as u32).sum()
+    }
+}
+
+/// List the near-melds in a hand: pairs, two-tile runs and gapped runs.
+///
+/// Each ordinary tile the hand does not hold is added in turn, and the
+/// solver's run and group generation finds the three-tile melds it would
+/// complete with two of the hand's ordinary tiles. Wilds are left out, since
+/// a wild already turns any near-meld into a meld. The result is sorted by
+/// unseen outs, most first.
+pub fn near_melds(hand: &Hand, unseen: &Hand, rules: &HouseRules) -> Vec<NearMeld> {
+    let mut naturals = Hand::new();
+    for (tile, &count) in hand.iter().filter(|(tile, _)| !tile.is_wild()) {
+        for _ in 0..count {
+            naturals.add(*tile);
+        }
+    }
+
+    let mut near: Vec<NearMeld> = Vec::new();
+    for color in 0..4 {
+        for number in 1..=13 {
+            let missing = Tile::new(color, number);
+            if naturals.count(&missing) > 0 {
+                continue;
+            }
+            let mut with_missing = naturals.clone();
+            with_missing.add(missing);
+
+            let mut melds = Vec::new();
+            solver::generate_runs_for_color(&with_missing, color, rules.wrap_around_runs, &mut melds);
+            solver::generate_groups_for_number(&with_missing, number, &mut melds);
+
+            for meld in melds.iter().filter(|m| m.tiles.len() == 3) {
+                let Some(position) = meld.tiles.iter().position(|t| *t == missing) else {
+                    continue;
+                };
+                let kind = match meld.meld_type {
+                    MeldType::Group => NearMeldKind::Pair,
+                    MeldType::Run if position == 1 => NearMeldKind::GappedRun,
+                    MeldType::Run => NearMeldKind::TwoTileRun,
+                };
+                let tiles: Vec<Tile> = meld.tiles.iter().copied().filter(|t| *t != missing).collect();
+                let completion = Completion { tile: missing, unseen: unseen.count(&missing) };
+
+                match near.iter_mut().find(|n| n.tiles == tiles) {
+                    Some(existing) => existing.completing.push(completion),
+                    None => near.push(NearMeld { kind, tiles, completing: vec![completion] }),
+                }
+            }
+        }
+    }
+
+    for meld in &mut near {
+        meld.completing.sort_by_key(|c| c.tile);
+    }
+    near.sort_by_key(|meld| std::cmp::Reverse(meld.outs()));
+    near
+}
+
+/// Whether one kind of hand tile can be played this turn, and how
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TilePlayability {
+    pub tile: Tile,
+    /// Copies of the tile in hand
+    pub copies: u8,
+    /// Some legal play this turn lays the tile down
+    pub playable: bool,
+    /// Table melds (by index) the tile can be added to on its own
+    pub attaches_to: Vec<usize>,
+    /// The tile cannot simply be added to a table meld, and the play found
+    /// for it rearranges the table beyond laying down new melds and extending
+    /// existing ones
+    pub needs_rearrangement: bool,
+}
+
+/// Playability of every tile kind in a hand
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayabilityMap {
+    /// One entry per tile kind in the hand, in tile order
+    pub tiles: Vec<TilePlayability>,
+    /// Every solver query finished its search within its share of the time
+    pub search_completed: bool,
+}
+
+/// Work out, for each tile kind in the hand, whether any legal play this
+/// turn uses it, not only the best play.
+///
+/// The solver runs in query mode (`solver::find_moves_playing`) once per tile
+/// kind, each with an equal share of `max_ms`. A tile the query cannot place
+/// within its share is reported as unplayable.
+pub fn playability_map(table: &Table, hand: &Hand, max_ms: u64, rules: &HouseRules) -> PlayabilityMap {
+    let share_ms = max_ms / hand.iter().count().max(1) as u64;
+    let mut search_completed = true;
+
+    let tiles = hand
+        .iter()
+        .map(|(&tile, &copies)| {
+            let mut table_copy = table.clone();
+            let mut hand_copy = hand.clone();
+            let result = solver::find_moves_playing(&mut table_copy, &mut hand_copy, tile, share_ms, rules);
+            search_completed &= result.search_completed;
+
+            let moves = result.moves;
+            let needs_rearrangement = moves.as_ref().is_some_and(|moves| {
+                solver::translate_to_human_moves_checked(table, hand, moves, rules)
+                    .iter()
+                    .any(|m| !matches!(m, HumanMove::PlayFromHand(_) | HumanMove::ExtendMeld { .. }))
+            });
+
+            let attaches_to = attachments(table, tile, rules);
+            TilePlayability {
+                tile,
+                copies,
+                playable: moves.is_some(),
+                needs_rearrangement: needs_rearrangement && attaches_to.is_empty(),
+                attaches_to,
+            }
+        })
+        .collect();
+
+    PlayabilityMap { tiles, search_completed }
+}
+
+/// Indices of the table melds that stay valid with `tile` added at an end
+fn attachments(table: &Table, tile: Tile, rules: &HouseRules) -> Vec<usize> {
+    table
+        .melds()
+        .iter()
+        .enumerate()
+        .filter(|(_, meld)| {
+            let mut front = (*meld).clone();
+            front.tiles.push_front(tile);
+            let mut back = (*meld).clone();
+            back.tiles.push_back(tile);
+            [front, back].iter().any(|m: &Meld| m.is_valid_with_wrap(rules.wrap_around_runs))
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Penalty points for a joker left in hand, as in standard end-of-game scoring
+const JOKER_PENALTY: i32 = 30;
+
+/// Whether to lay down the hand's jokers this turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JokerChoice {
+    /// Take the best play, jokers included
+    PlayJoker,
+    /// Take the best play that keeps every joker in hand
+    HoldJoker,
+}
+
+/// Comparison of the best play using the hand's jokers against the best play
+/// keeping them
+#[derive(Debug, Clone, PartialEq)]
+pub struct JokerAdvice {
+    pub recommendation: JokerChoice,
+    /// The best play with the whole hand, if any
+    pub with_joker: Option<Vec<SolverMove>>,
+    /// The best play with the hand's jokers set aside, if any
+    pub without_joker: Option<Vec<SolverMove>>,
+    /// Extra hand tiles the play with jokers lays down
+    pub tile_difference: i32,
+    /// Extra points the play with jokers lays down, a joker counting
+    /// `JOKER_PENALTY`
+    pub point_difference: i32,
+    /// Expected tiles left in hand after the lookahead when playing the jokers
+    pub expected_remaining_if_played: f64,
+    /// Expected tiles left in hand after the lookahead when holding them
+    pub expected_remaining_if_held: f64,
+    /// Samples simulated for each choice before the time ran out
+    pub samples_completed: usize,
+}
+
+impl JokerAdvice {
+    /// Tiles fewer expected in hand at the end of the lookahead by holding
+    /// the jokers (negative when playing them is better)
+    pub fn hold_value(&self) -> f64 {
+        self.expected_remaining_if_played - self.expected_remaining_if_held
+    }
+}
+
+/// Compare the best play this turn against the best play that keeps the
+/// hand's jokers.
+///
+/// The lookahead follows `advise_draw_or_play`: after each choice (drawing
+/// when it has no play) `settings.turns - 1` more own turns are simulated
+/// `settings.samples` times, with draws taken at random from `unseen`, and
+/// `settings.max_ms` is shared between the solver runs in the same way. Both
+/// choices see the same draw sequences. Holding is recommended only when it
+/// leaves fewer tiles expected.
+pub fn advise_joker(
+    table: &Table,
+    hand: &Hand,
+    unseen: &Hand,
+    strategy: ScoringStrategy,
+    rules: &HouseRules,
+    settings: &AdvisorSettings,
+) -> Result<JokerAdvice, String> {
+    let mut without_jokers = Hand::new();
+    for (tile, &count) in hand.iter().filter(|(tile, _)| !tile.is_wild()) {
+        for _ in 0..count {
+            without_jokers.add(*tile);
+        }
+    }
+    if without_jokers.len() == hand.len() {
+        return Err("Hand holds no joker".to_string());
+    }
+
+    // Both best plays, then the later turns of every sample for both choices
+    let lookahead = Lookahead::new(settings, 2);
+    let solve_ms = lookahead.solve_ms;
+    let with_joker = solve(table, hand, solve_ms, strategy, rules).moves.filter(|moves| !moves.is_empty());
+    let without_joker =
+        solve(table, &without_jokers, solve_ms, strategy, rules).moves.filter(|moves| !moves.is_empty());
+
+    let rack_after = |moves: &Option<Vec<SolverMove>>| {
+        let mut rack = hand.clone();
+        if let Some(moves) = moves {
+            solver::apply_moves(&mut table.clone(), &mut rack, moves);
+        }
+        rack
+    };
+    let (played_rack, held_rack) = (rack_after(&with_joker), rack_after(&without_joker));
+
+    let choices = [with_joker.as_deref(), without_joker.as_deref()];
+    let (expected, samples_completed) = lookahead.expected_remaining(table, hand, unseen, &choices, strategy, rules);
+    let (expected_remaining_if_played, expected_remaining_if_held) = (expected[0], expected[1]);
+
+    let recommendation = if expected_remaining_if_held < expected_remaining_if_played {
+        JokerChoice::HoldJoker
+    } else {
+        JokerChoice::PlayJoker
+    };
+
+    Ok(JokerAdvice {
+        recommendation,
+        tile_difference: held_rack.len() as i32 - played_rack.len() as i32,
+        point_difference: rack_points(&held_rack) - rack_points(&played_rack),
+        with_joker,
+        without_joker,
+        expected_remaining_if_played,
+        expected_remaining_if_held,
+        samples_completed,
+    })
+}
+
+/// The sampled lookahead shared by the advisors
+struct Lookahead {
+    timer: TimeTracker,
+    samples: usize,
+    later_turns: usize,
+    seed: u64,
+    /// Time for each solver run
+    solve_ms: u64,
+}
+
+impl Lookahead {
+    /// Share `settings.max_ms` equally between `first_solves` solver runs for
+    /// the current turn and the later turns of every sample for two choices
+    fn new(settings: &AdvisorSettings, first_solves: usize) -> Self {
+        let samples = settings.samples.max(1);
+        let later_turns = settings.turns.saturating_sub(1);
+        let runs = samples.saturating_mul(later_turns).saturating_mul(2).saturating_add(first_solves);
+        Lookahead {
+            timer: TimeTracker::new(settings.max_ms),
+            samples,
+            later_turns,
+            seed: settings.seed,
+            solve_ms: share_ms(settings.max_ms, runs),
+        }
+    }
+
+    /// Expected tiles left in hand after each first choice (a play, or a draw
+    /// for None) and the later turns, with the number of samples completed.
+    ///
+    /// Each choice has its own random source, seeded alike, so all of them see
+    /// the same draws. Once the time is up no further samples are started,
+    /// though at least one always is; the expectations average the samples
+    /// completed.
+    fn expected_remaining(
+        &self,
+        table: &Table,
+        hand: &Hand,
+        unseen: &Hand,
+        choices: &[Option<&[SolverMove]>],
+        strategy: ScoringStrategy,
+        rules: &HouseRules,
+    ) -> (Vec<f64>, usize) {
+        let mut rngs: Vec<SampleRng> = choices.iter().map(|_| SampleRng::new(self.seed)).collect();
+        let mut totals = vec![0; choices.len()];
+        let mut samples_completed = 0;
+        while samples_completed < self.samples && (samples_completed == 0 || !self.timer.is_expired()) {
+            for ((first, rng), total) in choices.iter().zip(&mut rngs).zip(&mut totals) {
+                *total += sample_remaining(
+                    table,
+                    hand,
+                    unseen,
+                    *first,
+                    strategy,
+                    rules,
+                    self.later_turns,
+                    self.solve_ms,
+                    rng,
+                );
+            }
+            samples_completed += 1;
+        }
+        let expected = totals.iter().map(|&total| total as f64 / samples_completed as f64).collect();
+        (expected, samples_completed)
+    }
+}
+
+/// Tiles in hand after taking `first` (or drawing, if None) and simulating
+/// `later_turns` more turns, for one draw sequence
+#[allow(clippy::too_many_arguments)]
+fn sample_remaining(
+    table: &Table,
+    hand: &Hand,
+    unseen: &Hand,
+    first: Option<&[SolverMove]>,
+    strategy: ScoringStrategy,
+    rules: &HouseRules,
+    later_turns: usize,
+    solve_ms: u64,
+    rng: &mut SampleRng,
+) -> usize {
+    let (mut table, mut hand, mut pool) = (table.clone(), hand.clone(), unseen.clone());
+    match first {
+        Some(moves) => solver::apply_moves(&mut table, &mut hand, moves),
+        None => {
+            if let Some(tile) = rng.draw(&mut pool) {
+                hand.add(tile);
+            }
+        }
+    }
+    simulate(&mut table, &mut hand, &mut pool, later_turns, strategy, rules, solve_ms, rng)
+}
+
+/// An equal share of `max_ms` for each of `runs` solver runs
+fn share_ms(max_ms: u64, runs: usize) -> u64 {
+    (max_ms / runs.max(1) as u64).max(1)
+}
+
+/// Penalty points of a rack: tile numbers, and `JOKER_PENALTY` per joker
+fn rack_points(hand: &Hand) -> i32 {
+    hand.iter()
+        .map(|(tile, &count)| tile.number().map_or(JOKER_PENALTY, i32::from) * count as i32)
+        .sum()
+}
+
+/// A meld the hand is one tile short of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearPlay {
+    /// The meld as it would be with the missing tile
+    pub meld: Meld,
+    /// The tile the hand lacks
+    pub missing: Tile,
+    /// Unseen copies of the missing tile
+    pub unseen: u8,
+}
+
+/// The melds a hand is one tile short of, to explain a position with no
+/// legal play: "one tile short of b 8 9 10". Built from `near_melds`, so
+/// the most likely completions come first.
+pub fn near_plays(hand: &Hand, unseen: &Hand, rules: &HouseRules) -> Vec<NearPlay> {
+    let mut plays: Vec<NearPlay> = near_melds(hand, unseen, rules)
+        .iter()
+        .flat_map(|near| {
+            near.completing.iter().map(move |completion| {
+                let mut tiles = near.tiles.clone();
+                tiles.push(completion.tile);
+                let meld_type = match near.kind {
+                    NearMeldKind::Pair => MeldType::Group,
+                    NearMeldKind::TwoTileRun | NearMeldKind::GappedRun => MeldType::Run,
+                };
+                NearPlay {
+                    meld: Meld::new(meld_type, tiles.into_iter().collect()),
+                    missing: completion.tile,
+                    unseen: completion.unseen,
+                }
+            })
+        })
+        .collect();
+    plays.sort_by_key(|play| std::cmp::Reverse(play.unseen));
+    plays
+}
+
+/// Highest hint level, at which the full play is given away
+pub const MAX_HINT_LEVEL: u8 = 4;
+
+/// A hint toward the best play, disclosing more at each level. Each level
+/// keeps what the lower levels disclosed:
+/// 1. how many hand tiles can be played
+/// 2. which table melds the play touches
+/// 3. which hand tiles it uses
+/// 4. the full list of moves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hint {
+    /// The level disclosed, 1 to `MAX_HINT_LEVEL`
+    pub level: u8,
+    /// Hand tiles the best play lays down (0 when there is no play)
+    pub tiles_played: usize,
+    /// Indices of the table melds the play picks up (level 2 and up)
+    pub table_melds: Option<Vec<usize>>,
+    /// Hand tiles the play lays down, in tile order (level 3 and up)
+    pub hand_tiles: Option<Vec<Tile>>,
+    /// The play as human-readable moves (level 4)
+    pub moves: Option<Vec<HumanMove>>,
+}
+
+/// Derive a hint at `level` (clamped to 1..=`MAX_HINT_LEVEL`) from a solver
+/// result for `table` and `hand`, found under `rules`.
+pub fn hint(table: &Table, hand: &Hand, result: &SolverResult, level: u8, rules: &HouseRules) -> Hint {
+    let level = level.clamp(1, MAX_HINT_LEVEL);
+    let moves = result.moves.as_deref().unwrap_or_default();
+    let hand_tiles = played_hand_tiles(table, hand, moves);
+    let table_melds: Vec<usize> = moves
+        .iter()
+        .filter_map(|m| match m {
+            SolverMove::PickUp(idx) => Some(*idx),
+            SolverMove::LayDown(_) => None,
+        })
+        .collect();
+
+    Hint {
+        level,
+        tiles_played: solver::tiles_played(table, moves),
+        table_melds: (level >= 2).then_some(table_melds),
+        hand_tiles: (level >= 3).then_some(hand_tiles),
+        moves: (level >= 4).then(|| solver::translate_to_human_moves_checked(table, hand, moves, rules)),
+    }
+}
+
+/// Hypothetical changes to a position, applied on copies of it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WhatIf {
+    /// Tiles added to the hand ("if I had b9")
+    pub add_to_hand: Vec<Tile>,
+    /// Tiles taken out of the hand
+    pub remove_from_hand: Vec<Tile>,
+    /// Indices of table melds taken away ("if the table didn't have that run")
+    pub remove_melds: Vec<usize>,
+    /// Melds added to the table
+    pub add_melds: Vec<Meld>,
+}
+
+/// The best play for a position and what it lays down
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub moves: Option<Vec<SolverMove>>,
+    /// Hand tiles the play lays down, in tile order
+    pub played: Vec<Tile>,
+    /// Tiles left in hand afterwards
+    pub remaining: usize,
+    pub search_completed: bool,
+}
+
+/// A what-if position solved next to the real one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhatIfComparison {
+    pub baseline: Outcome,
+    pub what_if: Outcome,
+    /// The hypothetical table and hand, which the what-if moves refer to
+    pub table: Table,
+    pub hand: Hand,
+    /// Extra hand tiles laid down in the what-if position
+    pub played_difference: i32,
+    /// Tiles laid down in the what-if position but not in the real one
+    pub newly_played: Vec<Tile>,
+    /// Tiles laid down in the real position but not in the what-if one
+    pub no_longer_played: Vec<Tile>,
+}
+
+/// Solve a position and a hypothetical variant of it, and compare the two.
+///
+/// The real table and hand are left alone. Each solve gets half of `max_ms`.
+/// Fails if the changes remove a tile the hand does not hold, name a table
+/// meld that does not exist, add a meld that is not valid under `rules`, or
+/// leave more copies of a tile in play than `tile_set` holds.
+pub fn what_if(
+    table: &Table,
+    hand: &Hand,
+    changes: &WhatIf,
+    max_ms: u64,
+    strategy: ScoringStrategy,
+    rules: &HouseRules,
+    tile_set: &TileSet,
+) -> Result<WhatIfComparison, String> {
+    let mut what_if_hand = hand.clone();
+    for tile in &changes.remove_from_hand {
+        if !what_if_hand.remove(tile) {
+            return Err(format!("Hand holds no {} to remove", tile));
+        }
+    }
+    for tile in &changes.add_to_hand {
+        what_if_hand.add(*tile);
+    }
+
+    let mut what_if_table = table.clone();
+    let mut removed = changes.remove_melds.clone();
+    removed.sort_unstable();
+    removed.dedup();
+    for &idx in removed.iter().rev() {
+        what_if_table
+            .remove_meld(idx)
+            .ok_or_else(|| format!("Table has no meld {} to remove", idx))?;
+    }
+    for meld in &changes.add_melds {
+        if !meld.is_valid_with_wrap(rules.wrap_around_runs) {
+            let tiles: Vec<String> = meld.tiles.iter().map(|t| t.to_string()).collect();
+            return Err(format!("Table meld to add is not valid: {}", tiles.join(" ")));
+        }
+        what_if_table.add_meld(meld.clone());
+    }
+
+    tile_set
+        .check(&what_if_hand, &what_if_table)
+        .map_err(|e| format!("What-if position: {}", e))?;
+
+    let share_ms = max_ms / 2;
+    let baseline = outcome(table, hand, share_ms, strategy, rules);
+    let what_if = outcome(&what_if_table, &what_if_hand, share_ms, strategy, rules);
+
+    Ok(WhatIfComparison {
+        played_difference: what_if.played.len() as i32 - baseline.played.len() as i32,
+        newly_played: tiles_missing_from(&what_if.played, &baseline.played),
+        no_longer_played: tiles_missing_from(&baseline.played, &what_if.played),
+        baseline,
+        what_if,
+        table: what_if_table,
+        hand: what_if_hand,
+    })
+}
+
+/// Solve a position and summarize its best play
+fn outcome(table: &Table, hand: &Hand, max_ms: u64, strategy: ScoringStrategy, rules: &HouseRules) -> Outcome {
+    let result = solve(table, hand, max_ms, strategy, rules);
+    let played = played_hand_tiles(table, hand, result.moves.as_deref().unwrap_or_default());
+    Outcome {
+        remaining: hand.len() - played.len(),
+        played,
+        moves: result.moves,
+        search_completed: result.search_completed,
+    }
+}
+
+/// The tiles of `tiles` left over once each tile of `other` has cancelled one copy
+fn tiles_missing_from(tiles: &[Tile], other: &[Tile]) -> Vec<Tile> {
+    let mut other = other.to_vec();
+    tiles
+        .iter()
+        .filter(|tile| match other.iter().position(|t| t == *tile) {
+            Some(pos) => {
+                other.swap_remove(pos);
+                false
+            }
+            None => true,
+        })
+        .copied()
+        .collect()
+}
+
+/// Hand tiles a move sequence lays down, in tile order
+fn played_hand_tiles(table: &Table, hand: &Hand, moves: &[SolverMove]) -> Vec<Tile> {
+    let mut rack = hand.clone();
+    solver::apply_moves(&mut table.clone(), &mut rack, moves);
+    hand.iter()
+        .flat_map(|(tile, &count)| std::iter::repeat_n(*tile, count.saturating_sub(rack.count(tile)) as usize))
+        .collect()
+}
+
+/// Run the solver on copies of the table and hand
+fn solve(table: &Table, hand: &Hand, max_ms: u64, strategy: ScoringStrategy, rules: &HouseRules) -> SolverResult {
+    let mut table_copy = table.clone();
+    let mut hand_copy = hand.clone();
+    solver::find_best_moves_with_rules(&mut table_copy, &mut hand_copy, max_ms, strategy, rules)
+}
+
+/// Small deterministic random source (SplitMix64) for sampling draws
+struct SampleRng(u64);
+
+impl SampleRng {
+    fn new(seed: u64) -> Self {
+        SampleRng(seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Take one tile out of the pool, each copy equally likely
+    fn draw(&mut self, pool: &mut Hand) -> Option<Tile> {
+        let size = pool.len();
+        if size == 0 {
+            return None;
+        }
+        let mut pick = (self.next() % size as u64) as usize;
+        let tile = pool.iter().find_map(|(tile, &count)| {
+            if pick < count as usize {
+                Some(*tile)
+            } else {
+                pick -= count as usize;
+                None
+            }
+        })?;
+        pool.remove(&tile);
+        Some(tile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Meld;
+
+    #[test]
+    fn test_draw_outlook_waiting_tile() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 5)); // R5
+        hand.add(Tile::new(0, 6)); // R6
+
+        let mut unseen = Hand::new();
+        unseen.add(Tile::new(0, 7)); // R7 completes the run
+        unseen.add(Tile::new(0, 4)); // So does R4
+        unseen.add(Tile::new(1, 9)); // B9 does nothing
+        unseen.add(Tile::new(1, 9));
+
+        let outlook = draw_outlook(&table, &hand, &unseen, 2000, ScoringStrategy::MinimizeTiles, &HouseRules::default());
+
+        assert_eq!(outlook.baseline_played, 0);
+        let r7 = outlook.outcomes.iter().find(|o| o.tile == Tile::new(0, 7)).unwrap();
+        assert!(r7.can_play && r7.goes_out);
+        assert_eq!(r7.tiles_played, 3);
+        let b9 = outlook.outcomes.iter().find(|o| o.tile == Tile::new(1, 9)).unwrap();
+        assert!(!b9.can_play);
+        assert_eq!(b9.unseen, 2);
+
+        assert!((outlook.useful_probability - 0.5).abs() < 1e-9);
+        assert!((outlook.out_probability - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_draw_outlook_when_hand_can_already_play() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        for tile in ["r1", "r2", "r3", "b5", "b6"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+
+        let mut unseen = Hand::new();
+        unseen.add(Tile::new(1, 7)); // B7 completes b5 b6
+        unseen.add(Tile::new(2, 11)); // Y11 does nothing
+
+        let outlook = draw_outlook(&table, &hand, &unseen, 2000, ScoringStrategy::MinimizeTiles, &HouseRules::default());
+
+        assert_eq!(outlook.baseline_played, 3);
+        let b7 = outlook.outcomes.iter().find(|o| o.tile == Tile::new(1, 7)).unwrap();
+        assert!(b7.can_play && b7.goes_out);
+        // The r1-r3 run is still played, but the drawn Y11 is not
+        let y11 = outlook.outcomes.iter().find(|o| o.tile == Tile::new(2, 11)).unwrap();
+        assert!(!y11.can_play);
+        assert_eq!(y11.tiles_played, 3);
+        assert!((outlook.useful_probability - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_draw_outlook_with_table_and_pool() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("b 1 2 3").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(2, 10)); // Y10
+
+        let unseen = TileSet::standard().unseen(&hand, &table, &Hand::new());
+        let outlook = draw_outlook(&table, &hand, &unseen, 3000, ScoringStrategy::MinimizeTiles, &HouseRules::default());
+
+        // Only B4 extends the table meld; Y10 stays in hand either way
+        let useful: Vec<Tile> = outlook.outcomes.iter().filter(|o| o.can_play).map(|o| o.tile).collect();
+        assert!(useful.contains(&Tile::new(1, 4)));
+        assert!(outlook.outcomes.iter().all(|o| !o.goes_out));
+        assert!(outlook.useful_probability > 0.0);
+    }
+
+    #[test]
+    fn test_advise_play_when_it_goes_out() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 5)); // R5
+        hand.add(Tile::new(0, 6)); // R6
+        hand.add(Tile::new(0, 7)); // R7
+
+        let unseen = TileSet::standard().unseen(&hand, &table, &Hand::new());
+        let settings = AdvisorSettings { samples: 4, ..AdvisorSettings::default() };
+        let advice = advise_draw_or_play(&table, &hand, &unseen, ScoringStrategy::MinimizeTiles, &HouseRules::default(), &settings);
+
+        assert_eq!(advice.recommendation, TurnChoice::Play);
+        assert_eq!(advice.expected_remaining_if_play, Some(0.0));
+        assert!(advice.expected_remaining_if_draw > 0.0);
+        assert_eq!(advice.samples_completed, 4);
+    }
+
+    #[test]
+    fn test_advise_stops_sampling_at_deadline() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        for tile in ["r5", "r6", "r7", "b9"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+
+        // Out of time at once: the single sample still run gives the averages
+        let unseen = TileSet::standard().unseen(&hand, &table, &Hand::new());
+        let settings = AdvisorSettings { max_ms: 0, ..AdvisorSettings::default() };
+        let advice = advise_draw_or_play(&table, &hand, &unseen, ScoringStrategy::MinimizeTiles, &HouseRules::default(), &settings);
+
+        assert_eq!(advice.samples_completed, 1);
+        assert!(advice.expected_remaining_if_play.is_some());
+        assert!(advice.expected_remaining_if_draw > 0.0);
+    }
+
+    #[test]
+    fn test_advise_huge_sample_count_keeps_to_budget() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        for tile in ["r5", "r6", "r7", "b9"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+
+        // The time shares must not overflow, and the deadline still ends the sampling
+        let unseen = TileSet::standard().unseen(&hand, &table, &Hand::new());
+        let settings = AdvisorSettings { samples: usize::MAX, max_ms: 50, ..AdvisorSettings::default() };
+        let advice = advise_draw_or_play(&table, &hand, &unseen, ScoringStrategy::MinimizeTiles, &HouseRules::default(), &settings);
+
+        assert!(advice.samples_completed >= 1);
+        assert!(advice.samples_completed < usize::MAX);
+    }
+
+    #[test]
+    fn test_advise_draw_when_no_play() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 5)); // R5
+        hand.add(Tile::new(1, 9)); // B9
+
+        let unseen = TileSet::standard().unseen(&hand, &table, &Hand::new());
+        let settings = AdvisorSettings { samples: 3, turns: 2, ..AdvisorSettings::default() };
+        let advice = advise_draw_or_play(&table, &hand, &unseen, ScoringStrategy::MinimizeTiles, &HouseRules::default(), &settings);
+
+        assert_eq!(advice.recommendation, TurnChoice::Draw);
+        assert!(advice.best_play.is_none());
+        assert_eq!(advice.expected_remaining_if_play, None);
+    }
+
+    #[test]
+    fn test_sample_rng_draws_whole_pool() {
+        let mut pool = Hand::new();
+        pool.add(Tile::new(0, 1));
+        pool.add(Tile::new(0, 1));
+        pool.add(Tile::wild());
+
+        let mut rng = SampleRng::new(7);
+        let mut drawn: Vec<Tile> = std::iter::from_fn(|| rng.draw(&mut pool)).collect();
+        drawn.sort();
+        assert_eq!(drawn, vec![Tile::new(0, 1), Tile::new(0, 1), Tile::wild()]);
+    }
+
+    #[test]
+    fn test_plan_spreads_melds_to_avoid_draws() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        for tile in ["r1", "r2", "r3", "b7", "y7", "k7", "y12"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+
+        // Playing both melds at once leaves nothing to play next turn
+        let plan = plan_turns(&table, &hand, 2, 500, ScoringStrategy::MinimizeTiles, &HouseRules::default());
+        assert_eq!(plan.final_rack, 1);
+        assert_eq!(plan.turns.len(), 2);
+        assert!(plan.turns.iter().all(|t| t.choice == TurnChoice::Play));
+    }
+
+    #[test]
+    fn test_plan_goes_out_at_once() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        for tile in ["r1", "r2", "r3", "b7", "y7", "k7"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+
+        let plan = plan_turns(&table, &hand, 3, 500, ScoringStrategy::MinimizeTiles, &HouseRules::default());
+        assert_eq!(plan.final_rack, 0);
+        assert_eq!(plan.turns.len(), 1);
+        assert_eq!(solver::tiles_played(&table, &plan.turns[0].moves), 6);
+    }
+
+    #[test]
+    fn test_plan_draws_without_plays() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 5)); // R5
+
+        let plan = plan_turns(&table, &hand, 2, 500, ScoringStrategy::MinimizeTiles, &HouseRules::default());
+        assert_eq!(plan.final_rack, 3);
+        assert!(plan.turns.iter().all(|t| t.choice == TurnChoice::Draw));
+    }
+
+    #[test]
+    fn test_plan_stops_at_deadline() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        for tile in ["r1", "r2", "r3", "b7", "y7", "k7"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+
+        // With no time the plays are never found, and every turn draws
+        let plan = plan_turns(&table, &hand, 3, 0, ScoringStrategy::MinimizeTiles, &HouseRules::default());
+        assert!(!plan.search_completed);
+        assert_eq!(plan.final_rack, 9);
+        assert!(plan.turns.iter().all(|t| t.choice == TurnChoice::Draw));
+
+        let plan = plan_turns(&table, &hand, 3, 2000, ScoringStrategy::MinimizeTiles, &HouseRules::default());
+        assert!(plan.search_completed);
+        assert_eq!(plan.final_rack, 0);
+    }
+
+    #[test]
+    fn test_plan_caps_turns() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 5)); // R5
+
+        // Every turn draws, one per planned turn, however many are asked for
+        let plan = plan_turns(&table, &hand, usize::MAX, 0, ScoringStrategy::MinimizeTiles, &HouseRules::default());
+        assert_eq!(plan.turns.len(), MAX_PLAN_TURNS);
+        assert_eq!(plan.final_rack, 1 + MAX_PLAN_TURNS);
+    }
+
+    #[test]
+    fn test_plan_caps_candidates() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        for tile in ["r1", "r2", "r3", "b5", "b6", "b7", "y9", "y10", "y11", "k1", "k2", "k3"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+
+        let rules = HouseRules::default();
+        let mut search = PlanSearch {
+            timer: TimeTracker::new(2000),
+            strategy: ScoringStrategy::MinimizeTiles,
+            rules: &rules,
+            completed: true,
+            solved: HashMap::new(),
+        };
+        let plays = search.candidate_plays(&table, &hand, 2);
+        assert_eq!(plays.len(), MAX_PLAN_CANDIDATES);
+        assert_eq!(solver::tiles_played(&table, &plays[0]), 12);
+        // Every single meld survives the cap, ahead of the larger subsets
+        assert!(plays[1..5].iter().all(|play| play.len() == 1));
+        assert_eq!(solver::tiles_played(&table, &plays[5]), 9);
+        assert_eq!(search.solved.len(), 1);
+    }
+
+    #[test]
+    fn test_plan_spreads_four_melds_over_four_turns() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        for tile in ["r1", "r2", "r3", "b5", "b6", "b7", "y9", "y10", "y11", "k1", "k2", "k3", "r9", "b12"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+
+        // One meld per turn never has to draw
+        let plan = plan_turns(&table, &hand, 4, 4000, ScoringStrategy::MinimizeTiles, &HouseRules::default());
+        assert_eq!(plan.final_rack, 2);
+        assert_eq!(plan.turns.len(), 4);
+        assert!(plan.turns.iter().all(|t| t.choice == TurnChoice::Play));
+    }
+
+    #[test]
+    fn test_near_melds_kinds_and_outs() {
+        let mut hand = Hand::new();
+        for tile in ["r5", "r6", "b9", "b11", "y2", "k2", "w"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+        let unseen = TileSet::standard().unseen(&hand, &Table::new(), &Hand::new());
+
+        let near = near_melds(&hand, &unseen, &HouseRules::default());
+        let find = |a: &str, b: &str| {
+            let tiles = vec![Tile::from_string(a).unwrap(), Tile::from_string(b).unwrap()];
+            near.iter().find(|n| n.tiles == tiles).cloned().expect("near-meld listed")
+        };
+
+        let run = find("r5", "r6");
+        assert_eq!(run.kind, NearMeldKind::TwoTileRun);
+        let completing: Vec<Tile> = run.completing.iter().map(|c| c.tile).collect();
+        assert_eq!(completing, vec![Tile::new(0, 4), Tile::new(0, 7)]);
+        assert_eq!(run.outs(), 4);
+
+        let gapped = find("b9", "b11");
+        assert_eq!(gapped.kind, NearMeldKind::GappedRun);
+        assert_eq!(gapped.completing.len(), 1);
+        assert_eq!(gapped.completing[0].tile, Tile::new(1, 10));
+
+        let pair = find("y2", "k2");
+        assert_eq!(pair.kind, NearMeldKind::Pair);
+        assert_eq!(pair.outs(), 4, "r2 and b2, two unseen copies each");
+
+        assert_eq!(near.len(), 3, "the wild does not form near-melds");
+    }
+
+    #[test]
+    fn test_near_melds_wrap_around() {
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 13)); // R13
+        hand.add(Tile::new(0, 1)); // R1
+        let mut unseen = Hand::new();
+        unseen.add(Tile::new(0, 12)); // R12
+
+        assert!(near_melds(&hand, &unseen, &HouseRules::default()).is_empty());
+
+        let rules = HouseRules { wrap_around_runs: true, ..HouseRules::default() };
+        let near = near_melds(&hand, &unseen, &rules);
+        assert_eq!(near.len(), 1);
+        assert_eq!(near[0].kind, NearMeldKind::TwoTileRun);
+        assert_eq!(near[0].outs(), 1);
+        assert_eq!(near[0].completing.len(), 2, "R12 and R2 both complete it");
+    }
+
+    #[test]
+    fn test_playability_map() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 3 4 5 6").unwrap());
+        table.add_meld(Meld::from_string("7 r b y").unwrap());
+        let mut hand = Hand::new();
+        for tile in ["r4", "r5", "k7", "b12"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+
+        let map = playability_map(&table, &hand, 4000, &HouseRules::default());
+        let entry = |s: &str| map.tiles.iter().find(|t| t.tile == Tile::from_string(s).unwrap()).unwrap();
+
+        assert!(map.search_completed);
+        assert_eq!(map.tiles.len(), 4);
+
+        let k7 = entry("k7");
+        assert!(k7.playable);
+        assert_eq!(k7.attaches_to, vec![1]);
+        assert!(!k7.needs_rearrangement);
+
+        let r4 = entry("r4");
+        assert!(r4.playable, "r3 r4 r5 and r4 r5 r6 split the table run");
+        assert!(r4.attaches_to.is_empty());
+        assert!(r4.needs_rearrangement);
+
+        let b12 = entry("b12");
+        assert!(!b12.playable);
+        assert!(b12.attaches_to.is_empty());
+    }
+
+    #[test]
+    fn test_advise_joker_differences() {
+        let table = Table::new();
+        let mut hand = Hand::new();
+        for tile in ["r1", "r2", "r3", "b9", "y9", "w", "k12"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+        let unseen = TileSet::standard().unseen(&hand, &table, &Hand::new());
+        let settings = AdvisorSettings { turns: 2, samples: 4, max_ms: 2000, seed: 7 };
+
+        let advice = advise_joker(&table, &hand, &unseen, ScoringStrategy::MinimizeTiles, &HouseRules::default(), &settings)
+            .unwrap();
+
+        // With the joker: r1 r2 r3 and 9 b y w. Without it: only the run.
+        assert!(advice.with_joker.is_some());
+        assert!(advice.without_joker.is_some());
+        assert_eq!(advice.tile_difference, 3);
+        assert_eq!(advice.point_difference, 9 + 9 + JOKER_PENALTY);
+        assert!(advice.expected_remaining_if_played >= 1.0);
+        assert_eq!(advice.hold_value(), advice.expected_remaining_if_played - advice.expected_remaining_if_held);
+    }
+
+    #[test]
+    fn test_lookahead_budget_saturates() {
+        let settings = AdvisorSettings { turns: usize::MAX, samples: usize::MAX, max_ms: 100, seed: 1 };
+        let lookahead = Lookahead::new(&settings, 2);
+        assert_eq!(lookahead.solve_ms, 1);
+        assert_eq!(lookahead.later_turns, usize::MAX - 1);
+    }
+
+    #[test]
+    fn test_advise_joker_needs_a_joker() {
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 1)); // R1
+        let settings = AdvisorSettings::default();
+
+        let result = advise_joker(&Table::new(), &hand, &Hand::new(), ScoringStrategy::MinimizeTiles, &HouseRules::default(), &settings);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_hint_levels() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 1 2 3").unwrap());
+        table.add_meld(Meld::from_string("9 b y k").unwrap());
+        let mut hand = Hand::new();
+        for tile in ["r4", "b5", "b6", "b7", "k12"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+        let result = solver::find_best_moves(&mut table.clone(), &mut hand.clone(), 1000);
+
+        let first = hint(&table, &hand, &result, 1, &HouseRules::default());
+        assert_eq!(first.tiles_played, 4);
+        assert_eq!(first.table_melds, None);
+        assert_eq!(first.moves, None);
+
+        let second = hint(&table, &hand, &result, 2, &HouseRules::default());
+        assert_eq!(second.table_melds, Some(vec![0]));
+        assert_eq!(second.hand_tiles, None);
+
+        let third = hint(&table, &hand, &result, 3, &HouseRules::default());
+        let expected: Vec<Tile> = ["r4", "b5", "b6", "b7"].iter().map(|s| Tile::from_string(s).unwrap()).collect();
+        assert_eq!(third.hand_tiles, Some(expected));
+
+        let full = hint(&table, &hand, &result, 9, &HouseRules::default());
+        assert_eq!(full.level, MAX_HINT_LEVEL);
+        assert_eq!(full.moves.map(|m| m.len()), Some(2));
+    }
+
+    #[test]
+    fn test_hint_without_play() {
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 1)); // R1
+        let result = solver::find_best_moves(&mut Table::new(), &mut hand.clone(), 500);
+
+        let full = hint(&Table::new(), &hand, &result, MAX_HINT_LEVEL, &HouseRules::default());
+        assert_eq!(full.tiles_played, 0);
+        assert_eq!(full.table_melds, Some(Vec::new()));
+        assert_eq!(full.hand_tiles, Some(Vec::new()));
+        assert_eq!(full.moves, Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_near_plays() {
+        let mut hand = Hand::new();
+        for tile in ["b8", "b10", "r2"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+        let unseen = TileSet::standard().unseen(&hand, &Table::new(), &Hand::new());
+
+        let plays = near_plays(&hand, &unseen, &HouseRules::default());
+
+        assert_eq!(plays.len(), 1);
+        assert_eq!(plays[0].meld, Meld::from_string("b 8 9 10").unwrap());
+        assert_eq!(plays[0].missing, Tile::new(1, 9));
+        assert_eq!(plays[0].unseen, 2);
+    }
+
+    #[test]
+    fn test_what_if_extra_tile() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 1 2 3").unwrap());
+        let mut hand = Hand::new();
+        for tile in ["b8", "b10", "k5"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+        let changes = WhatIf { add_to_hand: vec![Tile::from_string("b9").unwrap()], ..WhatIf::default() };
+
+        let (rules, standard) = (HouseRules::default(), TileSet::standard());
+        let comparison =
+            what_if(&table, &hand, &changes, 2000, ScoringStrategy::MinimizeTiles, &rules, &standard).unwrap();
+
+        assert!(comparison.baseline.moves.is_none());
+        assert_eq!(comparison.baseline.remaining, 3);
+        assert_eq!(comparison.what_if.remaining, 1);
+        assert_eq!(comparison.played_difference, 3);
+        let b = |n| Tile::new(1, n);
+        assert_eq!(comparison.newly_played, vec![b(8), b(9), b(10)]);
+        assert!(comparison.no_longer_played.is_empty());
+        assert_eq!(hand.count(&b(9)), 0, "the real hand is untouched");
+    }
+
+    #[test]
+    fn test_what_if_without_table_meld() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 1 2 3").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 4)); // R4
+        let changes = WhatIf { remove_melds: vec![0], ..WhatIf::default() };
+
+        let (rules, standard) = (HouseRules::default(), TileSet::standard());
+        let comparison =
+            what_if(&table, &hand, &changes, 2000, ScoringStrategy::MinimizeTiles, &rules, &standard).unwrap();
+
+        assert_eq!(comparison.no_longer_played, vec![Tile::new(0, 4)]);
+        assert_eq!(comparison.played_difference, -1);
+        assert!(comparison.table.is_empty());
+
+        let bad = WhatIf { remove_from_hand: vec![Tile::new(2, 2)], ..WhatIf::default() };
+        assert!(what_if(&table, &hand, &bad, 100, ScoringStrategy::MinimizeTiles, &rules, &standard).is_err());
+    }
+
+    #[test]
+    fn test_what_if_rejects_excess_tiles() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 4 5 6").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 4)); // R4
+        let (rules, standard) = (HouseRules::default(), TileSet::standard());
+
+        // A third R4 is one more than the standard set holds
+        let changes = WhatIf { add_to_hand: vec![Tile::new(0, 4)], ..WhatIf::default() };
+        let err = what_if(&table, &hand, &changes, 100, ScoringStrategy::MinimizeTiles, &rules, &standard).unwrap_err();
+        assert!(err.contains("r4 (3 of 2)"), "{}", err);
+
+        // Taking the table's R4 away makes room for it
+        let changes = WhatIf {
+            add_to_hand: vec![Tile::new(0, 4)],
+            remove_melds: vec![0],
+            ..WhatIf::default()
+        };
+        assert!(what_if(&table, &hand, &changes, 100, ScoringStrategy::MinimizeTiles, &rules, &standard).is_ok());
+    }
+
+    #[test]
+    fn test_what_if_rejects_invalid_meld() {
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 4)); // R4
+        let (rules, standard) = (HouseRules::default(), TileSet::standard());
+
+        let tiles = [Tile::new(0, 1), Tile::new(1, 5), Tile::new(2, 9)];
+        let changes = WhatIf {
+            add_melds: vec![Meld::new(MeldType::Run, tiles.into_iter().collect())],
+            ..WhatIf::default()
+        };
+        let err =
+            what_if(&Table::new(), &hand, &changes, 100, ScoringStrategy::MinimizeTiles, &rules, &standard).unwrap_err();
+        assert!(err.contains("r1 b5 y9"), "{}", err);
+    }
+}
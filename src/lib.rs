@@ -1,5 +1,8 @@
 use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
 
+pub mod analysis;
+pub mod okey;
 pub mod solver;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm_api;
@@ -7,7 +10,12 @@ pub mod wasm_api;
 /// A tile in Rummikub represented as a u8.
 /// - Bits 0-1: Color (00 = Red, 01 = Blue, 10 = Yellow, 11 = Black)
 /// - Bits 2-5: Number (1-13)
-/// - All 1s (0xFF): Wild/Joker
+/// - Bit 7 set: Wild/Joker, identified by the remaining bits:
+///   - All 1s (0xFF): the generic joker
+///   - 0x81-0x8F: numbered jokers 1-15, for editions whose jokers are told apart
+///   - 0xA1-0xA3: Rummikub Twist jokers (see `TwistPower`)
+///   - 0xC0-0xC3: color-restricted jokers, which may only stand for tiles of
+///     the color in bits 0-1
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Tile(u8);
 
@@ -16,11 +24,16 @@ impl Tile {
     const NUMBER_MASK: u8 = 0b0011_1100;
     const NUMBER_SHIFT: u8 = 2;
     const WILD: u8 = 0xFF;
+    const WILD_FLAG: u8 = 0b1000_0000;
+    const TWIST_WILD: u8 = 0b1010_0000;
+    const COLOR_WILD: u8 = 0b1100_0000;
+    const WILD_KIND_MASK: u8 = 0b1111_0000;
+    const JOKER_ID_MASK: u8 = 0b0000_1111;
 
     /// Create a new tile from color (0-3) and number (1-13)
     pub fn new(color: u8, number: u8) -> Self {
         assert!(color < 4, "Color must be 0-3");
-        assert!(number >= 1 && number <= 13, "Number must be 1-13");
+        assert!((1..=13).contains(&number), "Number must be 1-13");
         Tile((number << Self::NUMBER_SHIFT) | color)
     }
 
@@ -29,6 +42,29 @@ impl Tile {
         Tile(Self::WILD)
     }
 
+    /// Create a numbered joker (1-15) that can stand for any tile but is
+    /// distinguishable from the other jokers
+    pub fn joker(id: u8) -> Self {
+        assert!((1..=15).contains(&id), "Joker id must be 1-15");
+        Tile(Self::WILD_FLAG | id)
+    }
+
+    /// Create a Rummikub Twist joker with the given power
+    pub fn twist(power: TwistPower) -> Self {
+        let kind = match power {
+            TwistPower::Duplicate => 1,
+            TwistPower::ColorChange => 2,
+            TwistPower::Mirror => 3,
+        };
+        Tile(Self::TWIST_WILD | kind)
+    }
+
+    /// Create a joker that can only stand for tiles of the given color (0-3)
+    pub fn color_joker(color: u8) -> Self {
+        assert!(color < 4, "Color must be 0-3");
+        Tile(Self::COLOR_WILD | color)
+    }
+
     /// Get the color (0-3), or None for wild
     pub fn color(&self) -> Option<u8> {
         if self.is_wild() {
@@ -47,17 +83,78 @@ impl Tile {
         }
     }
 
-    /// Check if this is a wild/joker tile
+    /// Check if this is a wild/joker tile (of any kind)
     pub fn is_wild(&self) -> bool {
-        self.0 == Self::WILD
+        self.0 & Self::WILD_FLAG != 0
+    }
+
+    /// Get the id (1-15) of a numbered joker, or None for any other tile
+    pub fn joker_id(&self) -> Option<u8> {
+        if self.0 & Self::WILD_KIND_MASK == Self::WILD_FLAG {
+            Some(self.0 & Self::JOKER_ID_MASK)
+        } else {
+            None
+        }
+    }
+
+    /// Get the color (0-3) a color-restricted joker is limited to,
+    /// or None for unrestricted jokers and regular tiles
+    pub fn wild_color(&self) -> Option<u8> {
+        if self.0 & Self::WILD_KIND_MASK == Self::COLOR_WILD {
+            Some(self.0 & Self::COLOR_MASK)
+        } else {
+            None
+        }
+    }
+
+    /// Get the power of a Rummikub Twist joker, or None for any other tile
+    pub fn twist_power(&self) -> Option<TwistPower> {
+        if self.0 & Self::WILD_KIND_MASK != Self::TWIST_WILD {
+            return None;
+        }
+        match self.0 & Self::JOKER_ID_MASK {
+            1 => Some(TwistPower::Duplicate),
+            2 => Some(TwistPower::ColorChange),
+            3 => Some(TwistPower::Mirror),
+            _ => None,
+        }
+    }
+
+    /// Check if this wild can stand for the given tile as an ordinary joker:
+    /// unrestricted jokers stand for anything, color-restricted jokers only for
+    /// their color, and Twist jokers only act through their power
+    pub fn can_represent(&self, tile: Tile) -> bool {
+        self.is_wild()
+            && self.twist_power().is_none()
+            && match self.wild_color() {
+                Some(color) => tile.color() == Some(color),
+                None => true,
+            }
     }
 
     /// Parse a tile from a string representation
-    /// Format: "r13" (red 13), "b1" (blue 1), "y7" (yellow 7), "k9" (black 9), "w" (wild)
+    /// Format: "r13" (red 13), "b1" (blue 1), "y7" (yellow 7), "k9" (black 9), "w" (wild),
+    /// "w1"/"w2" (numbered jokers), "wr"/"wb"/"wy"/"wk" (color-restricted jokers),
+    /// "wd"/"wc"/"wm" (Twist duplicate, color-change and mirror jokers)
     pub fn from_string(s: &str) -> Result<Self, String> {
         if s == "w" {
             return Ok(Tile::wild());
         }
+        if let Some(kind) = s.strip_prefix('w') {
+            return match kind {
+                "d" => Ok(Tile::twist(TwistPower::Duplicate)),
+                "c" => Ok(Tile::twist(TwistPower::ColorChange)),
+                "m" => Ok(Tile::twist(TwistPower::Mirror)),
+                "r" => Ok(Tile::color_joker(0)),
+                "b" => Ok(Tile::color_joker(1)),
+                "y" => Ok(Tile::color_joker(2)),
+                "k" => Ok(Tile::color_joker(3)),
+                _ => match kind.parse::<u8>() {
+                    Ok(id) if (1..=15).contains(&id) => Ok(Tile::joker(id)),
+                    _ => Err(format!("Invalid joker: {}", s)),
+                },
+            };
+        }
         if s.len() < 2 {
             return Err(format!("Invalid tile string: {}", s));
         }
@@ -79,42 +176,70 @@ impl Tile {
 
         Ok(Tile::new(color, number))
     }
+}
 
-    /// Convert tile to string representation
-    /// Returns: "r13" (red 13), "b1" (blue 1), etc., or "w" for wild
-    pub fn to_string(&self) -> String {
+impl fmt::Display for Tile {
+    /// Format the tile using its string representation
+    /// Produces: "r13" (red 13), "b1" (blue 1), etc., or "w" for wild
+    /// ("w1" for numbered jokers, "wr" for color-restricted jokers)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.is_wild() {
-            return "w".to_string();
+            if let Some(id) = self.joker_id() {
+                return write!(f, "w{}", id);
+            }
+            match self.twist_power() {
+                Some(TwistPower::Duplicate) => return write!(f, "wd"),
+                Some(TwistPower::ColorChange) => return write!(f, "wc"),
+                Some(TwistPower::Mirror) => return write!(f, "wm"),
+                None => {}
+            }
+            return match self.wild_color() {
+                Some(0) => write!(f, "wr"),
+                Some(1) => write!(f, "wb"),
+                Some(2) => write!(f, "wy"),
+                Some(3) => write!(f, "wk"),
+                _ => write!(f, "w"),
+            };
         }
         let color_char = match self.color() {
             Some(0) => 'r',
             Some(1) => 'b',
             Some(2) => 'y',
             Some(3) => 'k',
-            None => {
-                // This shouldn't happen - we already checked is_wild()
-                eprintln!("ERROR: Tile has no color but is not wild. Raw value: 0x{:02x}", self.0);
-                return format!("?{}", self.0);
-            }
-            Some(c) => {
-                // This is mathematically impossible since color is 2 bits
-                eprintln!("ERROR: Invalid color value: {}. Raw tile value: 0x{:02x}", c, self.0);
-                return format!("?{}", self.0);
-            }
+            // Unreachable: color is 2 bits and wilds were handled above.
+            // Show the raw value rather than panicking mid-format.
+            _ => return write!(f, "?{}", self.0),
         };
         match self.number() {
-            Some(n) => format!("{}{}", color_char, n),
-            None => {
-                // Shouldn't happen for non-wild tiles
-                eprintln!("ERROR: Non-wild tile has no number. Raw value: 0x{:02x}", self.0);
-                format!("?{}", self.0)
-            }
+            Some(n) => write!(f, "{}{}", color_char, n),
+            // Shouldn't happen for non-wild tiles
+            None => write!(f, "?{}", self.0),
         }
     }
 }
 
+/// Special powers of the Rummikub Twist jokers.
+///
+/// A Twist joker never stands in as an ordinary joker; it only shapes the meld
+/// through its power. A meld may hold at most one Twist joker and no other
+/// jokers alongside it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TwistPower {
+    /// Repeats a tile of the meld. In a run it carries the same number as the
+    /// tile directly before it (r4 r5 wd r6 counts 4 5 5 6); in a group it is
+    /// a second copy of one of the colors present, so a group needs only two
+    /// real tiles and may reach five.
+    Duplicate,
+    /// Takes the next number of a run, after which the run goes on in another
+    /// color (r4 r5 wc b7 b8: the joker is the 6).
+    ColorChange,
+    /// Takes the next number of a run, after which the run counts back down
+    /// in the same color (r5 r6 wm r6 r5: the joker is the 7).
+    Mirror,
+}
+
 /// Type of meld in Rummikub
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MeldType {
     /// A group: same number, different colors
     Group,
@@ -122,8 +247,17 @@ pub enum MeldType {
     Run,
 }
 
+/// The tile a wild stands for within a meld
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WildAssignment {
+    /// Index of the wild in `Meld::tiles`
+    pub position: usize,
+    /// The tile the wild is played as
+    pub represents: Tile,
+}
+
 /// A meld (set of tiles) on the table
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Meld {
     pub meld_type: MeldType,
     pub tiles: VecDeque<Tile>,
@@ -134,9 +268,9 @@ impl Meld {
     fn canonical_tile_cmp(a: &Tile, b: &Tile) -> std::cmp::Ordering {
         use std::cmp::Ordering;
 
-        // Wild tiles should come last
+        // Wild tiles should come last, ordered by joker kind
         match (a.is_wild(), b.is_wild()) {
-            (true, true) => return Ordering::Equal,
+            (true, true) => return a.cmp(b),
             (true, false) => return Ordering::Greater,
             (false, true) => return Ordering::Less,
             _ => {}
@@ -157,13 +291,323 @@ impl Meld {
         }
     }
 
-    /// Create a new meld with tiles sorted in canonical order
+    /// Create a new meld with tiles in canonical order.
+    ///
+    /// Groups are sorted by color with wilds last. Runs are kept in sequence
+    /// order so that each wild stays in the slot it fills (see `arrange_run`).
     pub fn new(meld_type: MeldType, tiles: VecDeque<Tile>) -> Self {
-        let mut sorted_tiles: Vec<Tile> = tiles.into_iter().collect();
-        sorted_tiles.sort_by(Self::canonical_tile_cmp);
-        Meld {
-            meld_type,
-            tiles: sorted_tiles.into_iter().collect()
+        let tiles = match meld_type {
+            MeldType::Group => {
+                let mut sorted_tiles: Vec<Tile> = tiles.into_iter().collect();
+                sorted_tiles.sort_by(Self::canonical_tile_cmp);
+                sorted_tiles.into_iter().collect()
+            }
+            MeldType::Run => Self::arrange_run(tiles),
+        };
+        Meld { meld_type, tiles }
+    }
+
+    /// Put the tiles of a run in sequence order.
+    ///
+    /// If the tiles are already laid out as a consecutive sequence (wilds in the
+    /// slots they fill), that layout is kept, including a sequence that wraps
+    /// from 13 back to 1. Otherwise the numbered tiles are sorted, wilds fill the
+    /// gaps between them, and any spare wilds extend the run upwards (or
+    /// downwards once 13 is reached). The wilds placed are the ones given, and
+    /// a gap only takes a wild that can stand for the tile it fills.
+    fn arrange_run(tiles: VecDeque<Tile>) -> VecDeque<Tile> {
+        // Twist runs are shaped by their joker; only the given layout means anything
+        if Self::is_sequence_layout(&tiles)
+            || (Self::is_wrapped_layout(&tiles) && Self::wraps(&tiles))
+            || tiles.iter().any(|t| t.twist_power().is_some())
+        {
+            return tiles;
+        }
+
+        let mut numbered: Vec<Tile> = tiles.iter().copied().filter(|t| !t.is_wild()).collect();
+        let mut spare_wilds: Vec<Tile> = tiles.iter().copied().filter(|t| t.is_wild()).collect();
+        numbered.sort_by(Self::canonical_tile_cmp);
+
+        // Not a fillable sequence - fall back to sorted tiles with wilds last
+        let fallback = || {
+            let mut fallback: VecDeque<Tile> = numbered.iter().copied().collect();
+            fallback.extend(tiles.iter().filter(|t| t.is_wild()));
+            fallback
+        };
+
+        let mut arranged = VecDeque::new();
+        for tile in numbered.iter() {
+            if let (Some(prev), Some(number), Some(color)) =
+                (arranged.back().and_then(Tile::number), tile.number(), tile.color())
+            {
+                for missing in prev + 1..number {
+                    let Some(pos) = spare_wilds.iter().position(|w| w.can_represent(Tile::new(color, missing))) else {
+                        return fallback();
+                    };
+                    arranged.push_back(spare_wilds.remove(pos));
+                }
+            }
+            arranged.push_back(*tile);
+        }
+
+        let mut spare_wilds = spare_wilds.into_iter();
+        let mut last = numbered.last().and_then(Tile::number).unwrap_or(13);
+        while last < 13 {
+            let Some(wild) = spare_wilds.next() else {
+                break;
+            };
+            arranged.push_back(wild);
+            last += 1;
+        }
+        for wild in spare_wilds {
+            arranged.push_front(wild);
+        }
+        arranged
+    }
+
+    /// Check whether run tiles are already in sequence order: one color, and each
+    /// numbered tile sits exactly where its number puts it relative to the others.
+    fn is_sequence_layout(tiles: &VecDeque<Tile>) -> bool {
+        let mut start: Option<i32> = None;
+        let mut color: Option<u8> = None;
+        for (pos, tile) in tiles.iter().enumerate() {
+            let (Some(c), Some(n)) = (tile.color(), tile.number()) else {
+                continue;
+            };
+            if *color.get_or_insert(c) != c {
+                return false;
+            }
+            let tile_start = n as i32 - pos as i32;
+            if *start.get_or_insert(tile_start) != tile_start {
+                return false;
+            }
+        }
+        match start {
+            Some(s) => s >= 1 && s + tiles.len() as i32 - 1 <= 13,
+            None => true,
+        }
+    }
+
+    /// Check whether run tiles are in sequence order when counting wraps from
+    /// 13 back to 1: one color, at most 13 tiles, and each numbered tile sits
+    /// where its number puts it relative to the others.
+    fn is_wrapped_layout(tiles: &VecDeque<Tile>) -> bool {
+        let mut start: Option<i32> = None;
+        let mut color: Option<u8> = None;
+        for (pos, tile) in tiles.iter().enumerate() {
+            let (Some(c), Some(n)) = (tile.color(), tile.number()) else {
+                continue;
+            };
+            if *color.get_or_insert(c) != c {
+                return false;
+            }
+            let tile_start = (n as i32 - pos as i32).rem_euclid(13);
+            if *start.get_or_insert(tile_start) != tile_start {
+                return false;
+            }
+        }
+        tiles.len() <= 13
+    }
+
+    /// Check whether the numbered tiles of a run drop back somewhere (13 then 1)
+    fn wraps(tiles: &VecDeque<Tile>) -> bool {
+        let numbers: Vec<u8> = tiles.iter().filter_map(|t| t.number()).collect();
+        numbers.windows(2).any(|pair| pair[1] < pair[0])
+    }
+
+    /// The number a run slot holds, given its count from the run's first
+    /// number. Counts wrap around, so 14 is 1 and 0 is 13.
+    fn run_slot_number(slot: i32) -> u8 {
+        ((slot - 1).rem_euclid(13) + 1) as u8
+    }
+
+    /// Check that the meld is a legal group or run.
+    ///
+    /// Groups hold 3-4 tiles of one number in distinct colors; runs hold 3-13
+    /// consecutive numbers of one color in sequence order. Every wild must be
+    /// able to stand for its slot (see `wild_assignments`). Melds holding a
+    /// Twist joker follow its power instead (see `TwistPower`).
+    pub fn is_valid(&self) -> bool {
+        self.is_valid_with_wrap(false)
+    }
+
+    /// Check that the meld is legal, as `is_valid`, optionally letting runs
+    /// wrap around from 13 back to 1 (12-13-1-2).
+    pub fn is_valid_with_wrap(&self, wrap_around: bool) -> bool {
+        if self.tiles.len() < 3 {
+            return false;
+        }
+
+        let wild_count = self.tiles.iter().filter(|t| t.is_wild()).count();
+        if self.twist_joker().is_some() {
+            return match self.meld_type {
+                MeldType::Run => self.twist_run_tiles().is_some(),
+                MeldType::Group => self.is_valid_twist_group(),
+            };
+        }
+
+        match self.meld_type {
+            MeldType::Group => {
+                let numbered: Vec<&Tile> = self.tiles.iter().filter(|t| !t.is_wild()).collect();
+                let mut colors: Vec<u8> = numbered.iter().filter_map(|t| t.color()).collect();
+                colors.sort_unstable();
+                colors.dedup();
+
+                self.tiles.len() <= 4
+                    && colors.len() == numbered.len()
+                    && numbered.iter().all(|t| t.number() == numbered[0].number())
+                    && (numbered.is_empty() || self.wild_assignments().len() == wild_count)
+            }
+            MeldType::Run => {
+                let in_sequence = Self::is_sequence_layout(&self.tiles)
+                    || (wrap_around && Self::is_wrapped_layout(&self.tiles));
+                self.tiles.len() <= 13
+                    && in_sequence
+                    && (wild_count == self.tiles.len() || self.wild_assignments().len() == wild_count)
+            }
+        }
+    }
+
+    /// The Twist joker in the meld and its position, if any
+    fn twist_joker(&self) -> Option<(usize, TwistPower)> {
+        self.tiles
+            .iter()
+            .enumerate()
+            .find_map(|(i, t)| t.twist_power().map(|power| (i, power)))
+    }
+
+    /// The tile each position of a Twist run stands for, following the
+    /// joker's power. Returns None if the tiles do not form a valid Twist run.
+    fn twist_run_tiles(&self) -> Option<Vec<Tile>> {
+        let (joker_pos, power) = self.twist_joker()?;
+        if joker_pos == 0 || self.tiles.iter().enumerate().any(|(i, t)| i != joker_pos && t.is_wild()) {
+            return None;
+        }
+        // Color change and mirror need tiles on both sides of the joker
+        if power != TwistPower::Duplicate && joker_pos + 1 >= self.tiles.len() {
+            return None;
+        }
+
+        let mut stands_for: Vec<Tile> = Vec::with_capacity(self.tiles.len());
+        let mut step: i32 = 1;
+        for (i, tile) in self.tiles.iter().enumerate() {
+            if i == joker_pos {
+                let prev = stands_for.last()?;
+                let number = match power {
+                    TwistPower::Duplicate => prev.number()?,
+                    TwistPower::ColorChange | TwistPower::Mirror => prev.number()? + 1,
+                };
+                if number > 13 {
+                    return None;
+                }
+                stands_for.push(Tile::new(prev.color()?, number));
+                if power == TwistPower::Mirror {
+                    step = -1;
+                }
+                continue;
+            }
+
+            let (color, number) = (tile.color()?, tile.number()?);
+            if let Some(prev) = stands_for.last() {
+                if number as i32 != prev.number()? as i32 + step {
+                    return None;
+                }
+                let changes_color = i == joker_pos + 1 && power == TwistPower::ColorChange;
+                if changes_color == (color == prev.color()?) {
+                    return None;
+                }
+            }
+            stands_for.push(*tile);
+        }
+
+        Some(stands_for)
+    }
+
+    /// Check a group holding a Duplicate joker: at least two real tiles of one
+    /// number in distinct colors, at most five tiles in all.
+    fn is_valid_twist_group(&self) -> bool {
+        if self.twist_joker().map(|(_, power)| power) != Some(TwistPower::Duplicate) {
+            return false;
+        }
+        let numbered: Vec<&Tile> = self.tiles.iter().filter(|t| !t.is_wild()).collect();
+        let mut colors: Vec<u8> = numbered.iter().filter_map(|t| t.color()).collect();
+        colors.sort_unstable();
+        colors.dedup();
+
+        self.tiles.len() <= 5
+            && numbered.len() + 1 == self.tiles.len()
+            && numbered.len() >= 2
+            && colors.len() == numbered.len()
+            && numbered.iter().all(|t| t.number() == numbered[0].number())
+    }
+
+    /// The tile each wild in the meld is played as, in tile order.
+    ///
+    /// In a run the wild's slot fixes its number. In a group, color-restricted
+    /// jokers take their own color and the other wilds take the remaining
+    /// missing colors in color order; when a group has more missing colors than
+    /// wilds (a group of 3) any missing color is equally valid and the first
+    /// ones are reported. Wilds with no determinable tile (a meld made only of
+    /// wilds, or a joker of the wrong color) are left out. Run slots count on
+    /// from 13 to 1, so a wild after 13 in a wrapped run is played as a 1.
+    ///
+    /// A Twist joker in a run is reported as the tile its power makes it; a
+    /// Duplicate joker in a group as a copy of the group's first tile.
+    pub fn wild_assignments(&self) -> Vec<WildAssignment> {
+        if let Some((position, _)) = self.twist_joker() {
+            let represents = match self.meld_type {
+                MeldType::Run => self.twist_run_tiles().map(|tiles| tiles[position]),
+                MeldType::Group => self.tiles.iter().find(|t| !t.is_wild()).copied(),
+            };
+            return represents
+                .map(|represents| vec![WildAssignment { position, represents }])
+                .unwrap_or_default();
+        }
+
+        let wild_positions = self.tiles.iter().enumerate().filter(|(_, t)| t.is_wild()).map(|(i, _)| i);
+
+        match self.meld_type {
+            MeldType::Run => {
+                let Some((color, start)) = self.tiles.iter().enumerate().find_map(|(i, t)| {
+                    Some((t.color()?, t.number()? as i32 - i as i32))
+                }) else {
+                    return Vec::new();
+                };
+
+                wild_positions
+                    .filter_map(|position| {
+                        let represents = Tile::new(color, Self::run_slot_number(start + position as i32));
+                        self.tiles[position]
+                            .can_represent(represents)
+                            .then_some(WildAssignment { position, represents })
+                    })
+                    .collect()
+            }
+            MeldType::Group => {
+                let Some(number) = self.tiles.iter().find_map(|t| t.number()) else {
+                    return Vec::new();
+                };
+                let mut taken: Vec<u8> = self.tiles.iter().filter_map(|t| t.color()).collect();
+                taken.extend(self.tiles.iter().filter_map(|t| t.wild_color()));
+                let mut free_colors = (0..4).filter(|c| !taken.contains(c));
+
+                // Colors already filled, by numbered tiles or by a restricted joker
+                let mut filled: Vec<u8> = self.tiles.iter().filter_map(|t| t.color()).collect();
+                wild_positions
+                    .filter_map(|position| {
+                        let color = match self.tiles[position].wild_color() {
+                            Some(c) if !filled.contains(&c) => c,
+                            Some(_) => return None,
+                            None => free_colors.next()?,
+                        };
+                        filled.push(color);
+                        Some(WildAssignment {
+                            position,
+                            represents: Tile::new(color, number),
+                        })
+                    })
+                    .collect()
+            }
         }
     }
 
@@ -179,10 +623,10 @@ impl Meld {
 
         // Check if first token is a digit (1-13) → Group
         // Otherwise should be a color letter → Run
-        if let Ok(num) = tokens[0].parse::<u8>() {
-            if (1..=13).contains(&num) {
-                return Self::from_group_string(input);
-            }
+        if let Ok(num) = tokens[0].parse::<u8>()
+            && (1..=13).contains(&num)
+        {
+            return Self::from_group_string(input);
         }
 
         // Check if first token is a valid color
@@ -223,9 +667,9 @@ impl Meld {
                 "b" => 1,
                 "y" => 2,
                 "k" => 3,
-                "w" => {
-                    // Allow wildcard in groups
-                    tiles.push_back(Tile::wild());
+                wild if wild.starts_with('w') => {
+                    // Allow wildcards (of any joker kind) in groups
+                    tiles.push_back(Tile::from_string(wild)?);
                     continue;
                 }
                 _ => return Err(format!("Invalid color: {}", color_char)),
@@ -256,7 +700,7 @@ impl Meld {
             "b" => 1,
             "y" => 2,
             "k" => 3,
-            "w" => {
+            wild if wild.starts_with('w') => {
                 return Err("Wildcard cannot be the starting color of a run".to_string());
             }
             _ => return Err(format!("Invalid color: {}", tokens[0])),
@@ -265,8 +709,9 @@ impl Meld {
         // Parse numbers and create tiles
         let mut tiles = VecDeque::new();
         for num_str in &tokens[1..] {
-            if *num_str == "w" {
-                tiles.push_back(Tile::wild());
+            if num_str.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                // Jokers, or a full tile such as "b7" where a Twist run changes color
+                tiles.push_back(Tile::from_string(num_str)?);
             } else {
                 let number: u8 = num_str
                     .parse()
@@ -289,7 +734,7 @@ impl Meld {
 }
 
 /// A player's hand of tiles
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Hand(pub BTreeMap<Tile, u8>);
 
 impl Hand {
@@ -305,14 +750,14 @@ impl Hand {
 
     /// Remove a tile from the hand
     pub fn remove(&mut self, tile: &Tile) -> bool {
-        if let Some(count) = self.0.get_mut(tile) {
-            if *count > 0 {
-                *count -= 1;
-                if *count == 0 {
-                    self.0.remove(tile);
-                }
-                return true;
+        if let Some(count) = self.0.get_mut(tile)
+            && *count > 0
+        {
+            *count -= 1;
+            if *count == 0 {
+                self.0.remove(tile);
             }
+            return true;
         }
         false
     }
@@ -326,6 +771,16 @@ impl Hand {
     pub fn iter(&self) -> impl Iterator<Item = (&Tile, &u8)> {
         self.0.iter()
     }
+
+    /// Get the total number of tiles in the hand
+    pub fn len(&self) -> usize {
+        self.0.values().map(|&count| count as usize).sum()
+    }
+
+    /// Check if the hand holds no tiles
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 impl Default for Hand {
@@ -335,7 +790,7 @@ impl Default for Hand {
 }
 
 /// The table state (all melds currently on the table)
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Table(Vec<Meld>);
 
 impl Table {
@@ -385,6 +840,159 @@ impl Default for Table {
     }
 }
 
+/// The full set of tiles a game is played with.
+///
+/// The standard set holds two copies of each numbered tile and two jokers,
+/// 106 tiles in all. Jokers of every kind share the one joker total, since
+/// editions differ in which kinds of joker they print; each special joker
+/// (numbered, color-restricted or Twist) is also a single printed tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileSet {
+    /// Copies of each numbered tile
+    pub copies_per_tile: u8,
+    /// Jokers in the set, of any kind
+    pub jokers: u8,
+}
+
+/// A tile seen more often than the tile set holds copies of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileExcess {
+    /// The tile: a special joker printed more than once, or `Tile::wild()`
+    /// standing for jokers of every kind
+    pub tile: Tile,
+    /// Copies found in the hand and on the table
+    pub found: u8,
+    /// Copies the tile set holds
+    pub available: u8,
+}
+
+impl TileSet {
+    /// The standard 106-tile set
+    pub fn standard() -> Self {
+        TileSet {
+            copies_per_tile: 2,
+            jokers: 2,
+        }
+    }
+
+    /// Number of copies of a tile in the set: the joker total for
+    /// `Tile::wild()`, standing for jokers of every kind, and at most one for
+    /// each special joker
+    pub fn copies(&self, tile: Tile) -> u8 {
+        if tile == Tile::wild() {
+            self.jokers
+        } else if tile.is_wild() {
+            self.jokers.min(1)
+        } else {
+            self.copies_per_tile
+        }
+    }
+
+    /// Total number of tiles in the set
+    pub fn total(&self) -> usize {
+        52 * self.copies_per_tile as usize + self.jokers as usize
+    }
+
+    /// Every tile of the set, with jokers as `Tile::wild()`
+    pub fn full_set(&self) -> Hand {
+        let mut tiles = BTreeMap::new();
+        for number in 1..=13 {
+            for color in 0..4 {
+                tiles.insert(Tile::new(color, number), self.copies_per_tile);
+            }
+        }
+        tiles.insert(Tile::wild(), self.jokers);
+        tiles.retain(|_, copies| *copies > 0);
+        Hand(tiles)
+    }
+
+    /// The tiles not yet seen: the full set minus the hand, the table and any
+    /// tiles known to be in other racks (`known`). These are in the pool or in
+    /// opponents' racks. Jokers of every kind come out of the joker total and
+    /// remain as `Tile::wild()`; tiles seen more often than the set holds
+    /// simply have no copies left (see `excess`).
+    pub fn unseen(&self, hand: &Hand, table: &Table, known: &Hand) -> Hand {
+        let mut unseen = self.full_set();
+        let mut see = |tile: &Tile| {
+            let key = if tile.is_wild() { Tile::wild() } else { *tile };
+            unseen.remove(&key);
+        };
+        for (tile, &copies) in hand.iter().chain(known.iter()) {
+            for _ in 0..copies {
+                see(tile);
+            }
+        }
+        for meld in table.melds() {
+            meld.tiles.iter().for_each(&mut see);
+        }
+        unseen
+    }
+
+    /// Find the tiles the hand and table hold more copies of than the set has,
+    /// in tile order with jokers last. Each special joker may appear once, and
+    /// jokers of every kind together are checked against the joker total,
+    /// reported as `Tile::wild()`.
+    pub fn excess(&self, hand: &Hand, table: &Table) -> Vec<TileExcess> {
+        let mut found: BTreeMap<Tile, u8> = BTreeMap::new();
+        let mut count = |tile: Tile, copies: u8| {
+            let entry = found.entry(tile).or_insert(0);
+            *entry = entry.saturating_add(copies);
+        };
+        for (tile, &copies) in hand.iter() {
+            count(*tile, copies);
+        }
+        for meld in table.melds() {
+            for tile in &meld.tiles {
+                count(*tile, 1);
+            }
+        }
+
+        let jokers = found
+            .iter()
+            .filter(|(tile, _)| tile.is_wild())
+            .fold(0u8, |total, (_, &copies)| total.saturating_add(copies));
+        found.remove(&Tile::wild());
+
+        let mut excess: Vec<TileExcess> = found
+            .into_iter()
+            .filter(|&(tile, found)| found > self.copies(tile))
+            .map(|(tile, found)| TileExcess {
+                tile,
+                found,
+                available: self.copies(tile),
+            })
+            .collect();
+        if jokers > self.jokers {
+            excess.push(TileExcess {
+                tile: Tile::wild(),
+                found: jokers,
+                available: self.jokers,
+            });
+        }
+        excess
+    }
+
+    /// Fail, listing the tiles found too often, if the hand and table hold
+    /// more copies of a tile than the set has (see `excess`)
+    pub fn check(&self, hand: &Hand, table: &Table) -> Result<(), String> {
+        let excess = self.excess(hand, table);
+        if excess.is_empty() {
+            return Ok(());
+        }
+        let listed: Vec<String> = excess
+            .iter()
+            .map(|e| format!("{} ({} of {})", e.tile, e.found, e.available))
+            .collect();
+        Err(format!("More tiles than the tile set holds: {}", listed.join(", ")))
+    }
+}
+
+impl Default for TileSet {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -430,4 +1038,340 @@ mod tests {
             assert_eq!(tile, parsed);
         }
     }
+
+    #[test]
+    fn test_run_keeps_wild_positions() {
+        let low = Meld::from_string("r w 5 6").unwrap();
+        let high = Meld::from_string("r 5 6 w").unwrap();
+        assert_eq!(low.tiles, [Tile::wild(), Tile::new(0, 5), Tile::new(0, 6)]);
+        assert_eq!(high.tiles, [Tile::new(0, 5), Tile::new(0, 6), Tile::wild()]);
+        assert_ne!(low, high);
+    }
+
+    #[test]
+    fn test_run_arranged_from_unordered_tiles() {
+        // Wild fills the gap between 5 and 7
+        let tiles = VecDeque::from([Tile::wild(), Tile::new(1, 7), Tile::new(1, 5)]);
+        let meld = Meld::new(MeldType::Run, tiles);
+        assert_eq!(meld.tiles, [Tile::new(1, 5), Tile::wild(), Tile::new(1, 7)]);
+
+        // Spare wild goes below once the run reaches 13
+        let tiles = VecDeque::from([Tile::new(2, 13), Tile::wild(), Tile::new(2, 12)]);
+        let meld = Meld::new(MeldType::Run, tiles);
+        assert_eq!(meld.tiles, [Tile::wild(), Tile::new(2, 12), Tile::new(2, 13)]);
+    }
+
+    #[test]
+    fn test_run_arrangement_keeps_joker_kinds() {
+        let tiles = VecDeque::from([Tile::joker(1), Tile::new(0, 7), Tile::new(0, 5)]);
+        let meld = Meld::new(MeldType::Run, tiles);
+        assert_eq!(meld.tiles, [Tile::new(0, 5), Tile::joker(1), Tile::new(0, 7)]);
+
+        let tiles = VecDeque::from([Tile::color_joker(0), Tile::new(0, 5), Tile::new(0, 7)]);
+        let meld = Meld::new(MeldType::Run, tiles);
+        assert_eq!(meld.tiles, [Tile::new(0, 5), Tile::color_joker(0), Tile::new(0, 7)]);
+
+        // A joker restricted to another color cannot fill the gap
+        let tiles = VecDeque::from([Tile::color_joker(1), Tile::new(0, 5), Tile::new(0, 7)]);
+        let meld = Meld::new(MeldType::Run, tiles);
+        assert_eq!(meld.tiles, [Tile::new(0, 5), Tile::new(0, 7), Tile::color_joker(1)]);
+    }
+
+    #[test]
+    fn test_wild_assignments_run() {
+        let meld = Meld::from_string("r 5 6 w").unwrap();
+        assert_eq!(
+            meld.wild_assignments(),
+            vec![WildAssignment { position: 2, represents: Tile::new(0, 7) }]
+        );
+
+        let meld = Meld::from_string("b w 5 w").unwrap();
+        assert_eq!(
+            meld.wild_assignments(),
+            vec![
+                WildAssignment { position: 0, represents: Tile::new(1, 4) },
+                WildAssignment { position: 2, represents: Tile::new(1, 6) },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wild_assignments_group() {
+        // Group of 4: the wild is the one missing color
+        let meld = Meld::from_string("9 r b k w").unwrap();
+        assert_eq!(
+            meld.wild_assignments(),
+            vec![WildAssignment { position: 3, represents: Tile::new(2, 9) }]
+        );
+
+        // Two wilds take distinct missing colors
+        let meld = Meld::from_string("7 r w w").unwrap();
+        let represented: Vec<Tile> = meld.wild_assignments().iter().map(|a| a.represents).collect();
+        assert_eq!(represented, vec![Tile::new(1, 7), Tile::new(2, 7)]);
+
+        // No wilds, no assignments
+        assert!(Meld::from_string("4 r b y").unwrap().wild_assignments().is_empty());
+    }
+
+    #[test]
+    fn test_joker_kinds_from_string() {
+        assert_eq!(Tile::from_string("w1").unwrap(), Tile::joker(1));
+        assert_eq!(Tile::from_string("w2").unwrap(), Tile::joker(2));
+        assert_eq!(Tile::from_string("wr").unwrap(), Tile::color_joker(0));
+        assert_eq!(Tile::from_string("wk").unwrap(), Tile::color_joker(3));
+
+        assert_ne!(Tile::joker(1), Tile::joker(2));
+        assert_ne!(Tile::joker(1), Tile::wild());
+        for tile in [Tile::joker(1), Tile::joker(15), Tile::color_joker(2), Tile::wild()] {
+            assert!(tile.is_wild());
+            assert_eq!(tile.color(), None);
+            assert_eq!(tile.number(), None);
+            assert_eq!(Tile::from_string(&tile.to_string()).unwrap(), tile);
+        }
+
+        assert!(Tile::from_string("w0").is_err());
+        assert!(Tile::from_string("w16").is_err());
+        assert!(Tile::from_string("wx").is_err());
+    }
+
+    #[test]
+    fn test_joker_kind_properties() {
+        assert_eq!(Tile::joker(2).joker_id(), Some(2));
+        assert_eq!(Tile::wild().joker_id(), None);
+        assert_eq!(Tile::color_joker(1).joker_id(), None);
+
+        assert_eq!(Tile::color_joker(1).wild_color(), Some(1));
+        assert_eq!(Tile::wild().wild_color(), None);
+        assert_eq!(Tile::joker(3).wild_color(), None);
+        assert_eq!(Tile::new(1, 5).wild_color(), None);
+
+        assert!(Tile::color_joker(1).can_represent(Tile::new(1, 5)));
+        assert!(!Tile::color_joker(1).can_represent(Tile::new(0, 5)));
+        assert!(Tile::joker(1).can_represent(Tile::new(0, 5)));
+        assert!(!Tile::new(0, 5).can_represent(Tile::new(0, 5)));
+    }
+
+    #[test]
+    fn test_meld_with_joker_kinds() {
+        let run = Meld::from_string("r 5 w1 7").unwrap();
+        assert_eq!(run.tiles, [Tile::new(0, 5), Tile::joker(1), Tile::new(0, 7)]);
+
+        // Restricted joker takes its own color, the other wild the next free color
+        let group = Meld::from_string("7 r w wk").unwrap();
+        for assignment in group.wild_assignments() {
+            let expected = if group.tiles[assignment.position] == Tile::color_joker(3) {
+                Tile::new(3, 7)
+            } else {
+                Tile::new(1, 7)
+            };
+            assert_eq!(assignment.represents, expected);
+        }
+        assert_eq!(group.wild_assignments().len(), 2);
+
+        // A restricted joker of a color already present stands for nothing
+        let group = Meld::from_string("7 r b wr").unwrap();
+        assert!(group.wild_assignments().is_empty());
+    }
+
+    #[test]
+    fn test_twist_jokers_from_string() {
+        assert_eq!(Tile::from_string("wd").unwrap(), Tile::twist(TwistPower::Duplicate));
+        assert_eq!(Tile::from_string("wc").unwrap(), Tile::twist(TwistPower::ColorChange));
+        assert_eq!(Tile::from_string("wm").unwrap(), Tile::twist(TwistPower::Mirror));
+
+        for power in [TwistPower::Duplicate, TwistPower::ColorChange, TwistPower::Mirror] {
+            let tile = Tile::twist(power);
+            assert!(tile.is_wild());
+            assert_eq!(tile.twist_power(), Some(power));
+            assert_eq!(tile.joker_id(), None);
+            assert_eq!(tile.wild_color(), None);
+            assert!(!tile.can_represent(Tile::new(0, 5)));
+            assert_eq!(Tile::from_string(&tile.to_string()).unwrap(), tile);
+        }
+        assert_eq!(Tile::wild().twist_power(), None);
+    }
+
+    #[test]
+    fn test_twist_meld_validity() {
+        let valid = [
+            "r 4 5 wd 6",    // 4 5 5 6
+            "r 4 5 wd",      // 4 5 5
+            "r 4 5 wc b7 b8", // red 4 5 6, then blue 7 8
+            "r 5 6 wm 6 5",  // 5 6 7 6 5
+            "7 r b wd",      // two colors, one doubled
+            "7 r b y k wd",  // five tiles
+        ];
+        for input in valid {
+            let meld = Meld::from_string(input).unwrap();
+            assert!(meld.is_valid(), "{input} should be valid");
+        }
+
+        let invalid = [
+            "r wd 4 5",      // nothing to duplicate
+            "r 4 5 wc r7",   // color must change
+            "r 4 5 wc b8",   // right side must continue the count
+            "r 4 5 wc",      // color change needs tiles after it
+            "r 5 6 wm 7",    // mirror counts back down
+            "r 4 wd 5 w",    // no other jokers with a Twist joker
+            "7 r wd w",      // no other jokers in a Twist group
+            "7 r b wc",      // only the Duplicate joker works in groups
+        ];
+        for input in invalid {
+            let meld = Meld::from_string(input).unwrap();
+            assert!(!meld.is_valid(), "{input} should be invalid");
+        }
+    }
+
+    #[test]
+    fn test_meld_is_valid_standard() {
+        assert!(Meld::from_string("r 5 6 7").unwrap().is_valid());
+        assert!(Meld::from_string("r 5 w 7").unwrap().is_valid());
+        assert!(Meld::from_string("7 r b w").unwrap().is_valid());
+        assert!(!Meld::from_string("7 r b wr").unwrap().is_valid());
+        assert!(!Meld::new(MeldType::Run, VecDeque::from(vec![Tile::new(0, 5), Tile::new(1, 6), Tile::new(0, 7)])).is_valid());
+    }
+
+    #[test]
+    fn test_twist_wild_assignments() {
+        let duplicate = Meld::from_string("r 4 5 wd 6").unwrap();
+        assert_eq!(duplicate.wild_assignments(), vec![WildAssignment { position: 2, represents: Tile::new(0, 5) }]);
+
+        let color_change = Meld::from_string("r 4 5 wc b7").unwrap();
+        assert_eq!(color_change.wild_assignments(), vec![WildAssignment { position: 2, represents: Tile::new(0, 6) }]);
+
+        let mirror = Meld::from_string("r 5 6 wm 6").unwrap();
+        assert_eq!(mirror.wild_assignments(), vec![WildAssignment { position: 2, represents: Tile::new(0, 7) }]);
+    }
+
+    #[test]
+    fn test_wrapped_run() {
+        let run = Meld::from_string("r 12 13 1 2").unwrap();
+        assert_eq!(run.tiles, [Tile::new(0, 12), Tile::new(0, 13), Tile::new(0, 1), Tile::new(0, 2)]);
+        assert!(!run.is_valid());
+        assert!(run.is_valid_with_wrap(true));
+
+        let run = Meld::from_string("r 13 w 2").unwrap();
+        assert!(run.is_valid_with_wrap(true));
+        assert_eq!(run.wild_assignments(), vec![WildAssignment { position: 1, represents: Tile::new(0, 1) }]);
+
+        // Still one color, one step at a time
+        assert!(!Meld::from_string("r 12 13 2").unwrap().is_valid_with_wrap(true));
+        assert!(!Meld::from_string("r 12 b13 1").unwrap().is_valid_with_wrap(true));
+        assert!(Meld::from_string("r 5 6 7").unwrap().is_valid_with_wrap(true));
+    }
+
+    #[test]
+    fn test_tile_set_standard() {
+        let set = TileSet::default();
+        assert_eq!(set.total(), 106);
+        assert_eq!(set.copies(Tile::new(0, 5)), 2);
+        assert_eq!(set.copies(Tile::wild()), 2);
+        assert_eq!(set.copies(Tile::joker(1)), 1);
+        assert_eq!(TileSet { copies_per_tile: 2, jokers: 0 }.copies(Tile::joker(1)), 0);
+        assert_eq!(TileSet { copies_per_tile: 2, jokers: 4 }.total(), 108);
+    }
+
+    #[test]
+    fn test_tile_set_excess() {
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 5));
+        hand.add(Tile::new(0, 5));
+        hand.add(Tile::wild());
+        hand.add(Tile::wild());
+        hand.add(Tile::joker(1));
+
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 4 5 6").unwrap());
+        table.add_meld(Meld::from_string("7 r b w").unwrap());
+
+        let excess = TileSet::standard().excess(&hand, &table);
+        assert_eq!(
+            excess,
+            vec![
+                TileExcess { tile: Tile::new(0, 5), found: 3, available: 2 },
+                TileExcess { tile: Tile::wild(), found: 4, available: 2 },
+            ]
+        );
+
+        // A set with four jokers has room for them
+        let set = TileSet { copies_per_tile: 2, jokers: 4 };
+        assert_eq!(set.excess(&hand, &table).len(), 1);
+        assert!(TileSet::standard().excess(&Hand::new(), &Table::new()).is_empty());
+
+        let err = TileSet::standard().check(&hand, &table).unwrap_err();
+        assert_eq!(err, "More tiles than the tile set holds: r5 (3 of 2), w (4 of 2)");
+        assert!(set.check(&Hand::new(), &table).is_ok());
+    }
+
+    #[test]
+    fn test_tile_set_excess_special_jokers() {
+        // Special jokers come out of the joker total, and each is printed once
+        let set = TileSet { copies_per_tile: 2, jokers: 8 };
+        let mut hand = Hand::new();
+        for joker in [
+            Tile::wild(),
+            Tile::wild(),
+            Tile::joker(1),
+            Tile::joker(2),
+            Tile::color_joker(0),
+            Tile::twist(TwistPower::Duplicate),
+            Tile::twist(TwistPower::ColorChange),
+            Tile::twist(TwistPower::Mirror),
+        ] {
+            hand.add(joker);
+        }
+        assert!(set.excess(&hand, &Table::new()).is_empty());
+
+        hand.add(Tile::joker(1));
+        assert_eq!(
+            set.excess(&hand, &Table::new()),
+            vec![
+                TileExcess { tile: Tile::joker(1), found: 2, available: 1 },
+                TileExcess { tile: Tile::wild(), found: 9, available: 8 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tile_set_mixed_jokers() {
+        // Plain and special jokers share the standard set's two jokers
+        let set = TileSet::standard();
+        let mut hand = Hand::new();
+        hand.add(Tile::wild());
+        hand.add(Tile::twist(TwistPower::Duplicate));
+        assert!(set.excess(&hand, &Table::new()).is_empty());
+        let unseen = set.unseen(&hand, &Table::new(), &Hand::new());
+        assert_eq!(unseen.count(&Tile::wild()), 0);
+        assert_eq!(unseen.len(), set.total() - 2);
+
+        hand.add(Tile::wild());
+        assert_eq!(
+            set.excess(&hand, &Table::new()),
+            vec![TileExcess { tile: Tile::wild(), found: 3, available: 2 }]
+        );
+    }
+
+    #[test]
+    fn test_tile_set_unseen() {
+        let set = TileSet::standard();
+        assert_eq!(set.full_set().len(), 106);
+
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 5));
+        hand.add(Tile::joker(2));
+
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 4 5 6").unwrap());
+
+        let mut known = Hand::new();
+        known.add(Tile::new(1, 9));
+
+        let unseen = set.unseen(&hand, &table, &known);
+        assert_eq!(unseen.count(&Tile::new(0, 5)), 0);
+        assert_eq!(unseen.count(&Tile::new(0, 4)), 1);
+        assert_eq!(unseen.count(&Tile::new(1, 9)), 1);
+        assert_eq!(unseen.count(&Tile::new(3, 13)), 2);
+        assert_eq!(unseen.count(&Tile::wild()), 1);
+        assert_eq!(unseen.len(), 106 - 6);
+    }
 }
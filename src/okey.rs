@@ -0,0 +1,535 @@
+//! Okey (Turkish rummy) variant.
+//!
+//! Okey is played with the same 106 tiles as Rummikub, but melds are only
+//! ever formed inside a player's own hand:
+//! - Runs may wrap from 13 back to 1 (12-13-1), with 1 as the last tile.
+//! - The wild ("okey") is not a printed joker: it is the tile one above the
+//!   face-up indicator in the indicator's color (13 wraps to 1), and both
+//!   copies of it are wild.
+//! - The two printed jokers are "false jokers": they stand for the okey tile
+//!   as an ordinary tile.
+//! - A hand wins when its 14 tiles all form melds, or form seven pairs.
+//!
+//! Hands are read with printed jokers as `Tile::wild()`. Internally they are
+//! normalized (see `OkeyRules::normalize`), so the okey tiles become wilds and
+//! the false jokers become the okey tile. Results come back in the
+//! normalized form; use `OkeyRules::physical_tile` to name the tile to pick up.
+
+use crate::{Hand, Meld, MeldType, Tile};
+use std::collections::VecDeque;
+
+/// Number of tiles in a finished Okey hand
+pub const WINNING_HAND_SIZE: usize = 14;
+
+/// The Okey rules in force for a deal, fixed by its indicator tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OkeyRules {
+    indicator: Tile,
+}
+
+/// A finished Okey hand
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OkeyWin {
+    /// Every tile is in a group or run
+    Melds(Vec<Meld>),
+    /// Seven pairs of identical tiles (a wild pairs with anything)
+    SevenPairs(Vec<(Tile, Tile)>),
+}
+
+/// How a hand is best arranged, whether or not it wins
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OkeyArrangement {
+    /// Groups and runs, with the tiles outside them left over
+    Melds(Vec<Meld>),
+    /// Pairs of identical tiles (a wild pairs with anything), with the
+    /// unpaired tiles left over
+    Pairs(Vec<(Tile, Tile)>),
+}
+
+/// The solver's choice of tile to discard from a 15-tile hand
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OkeyDiscard {
+    /// Tile to discard, in normalized form
+    pub discard: Tile,
+    /// Tiles left outside any meld (or pair) after the discard
+    pub unmatched: usize,
+    /// The arrangement of the remaining tiles that leaves `unmatched` over
+    pub arrangement: OkeyArrangement,
+}
+
+impl OkeyDiscard {
+    /// The remaining 14 tiles form a winning hand
+    pub fn wins(&self) -> bool {
+        self.unmatched == 0
+    }
+
+    /// How the remaining 14 tiles win, if they do
+    pub fn win(&self) -> Option<OkeyWin> {
+        self.wins().then(|| self.arrangement.clone().into_win())
+    }
+}
+
+impl OkeyArrangement {
+    /// The winning hand this arrangement makes when no tile is left over
+    fn into_win(self) -> OkeyWin {
+        match self {
+            OkeyArrangement::Melds(melds) => OkeyWin::Melds(melds),
+            OkeyArrangement::Pairs(pairs) => OkeyWin::SevenPairs(pairs),
+        }
+    }
+}
+
+impl OkeyRules {
+    /// Set up the rules for a deal with the given face-up indicator tile
+    pub fn new(indicator: Tile) -> Result<Self, String> {
+        if indicator.is_wild() {
+            return Err(format!("Indicator must be a numbered tile, got {}", indicator));
+        }
+        Ok(OkeyRules { indicator })
+    }
+
+    /// The face-up indicator tile
+    pub fn indicator(&self) -> Tile {
+        self.indicator
+    }
+
+    /// The okey tile: one above the indicator in its color, 13 wrapping to 1
+    pub fn okey_tile(&self) -> Tile {
+        let color = self.indicator.color().unwrap();
+        let number = self.indicator.number().unwrap();
+        Tile::new(color, number % 13 + 1)
+    }
+
+    /// Map a physical tile to the role it plays: okey tiles become wilds and
+    /// false jokers become the okey tile
+    pub fn normalize(&self, tile: Tile) -> Tile {
+        if tile == self.okey_tile() {
+            Tile::wild()
+        } else if tile.is_wild() {
+            self.okey_tile()
+        } else {
+            tile
+        }
+    }
+
+    /// Map a normalized tile back to the physical tile that plays it
+    pub fn physical_tile(&self, tile: Tile) -> Tile {
+        if tile.is_wild() {
+            self.okey_tile()
+        } else if tile == self.okey_tile() {
+            Tile::wild()
+        } else {
+            tile
+        }
+    }
+
+    /// Normalize every tile of a hand
+    pub fn normalize_hand(&self, hand: &Hand) -> Hand {
+        let mut normalized = Hand::new();
+        for (tile, &count) in hand.iter() {
+            for _ in 0..count {
+                normalized.add(self.normalize(*tile));
+            }
+        }
+        normalized
+    }
+}
+
+/// Check a meld against the Okey rules: groups of 3-4 distinct colors of one
+/// number, or runs of 3-13 consecutive numbers of one color that may end by
+/// wrapping from 13 to 1. Wilds fill any slot.
+pub fn is_valid_okey_meld(meld: &Meld) -> bool {
+    let len = meld.tiles.len();
+    match meld.meld_type {
+        MeldType::Group => {
+            let numbered: Vec<&Tile> = meld.tiles.iter().filter(|t| !t.is_wild()).collect();
+            let mut colors: Vec<u8> = numbered.iter().filter_map(|t| t.color()).collect();
+            colors.sort_unstable();
+            colors.dedup();
+
+            (3..=4).contains(&len)
+                && colors.len() == numbered.len()
+                && numbered.iter().all(|t| t.number() == numbered[0].number())
+        }
+        MeldType::Run => {
+            let mut colors = meld.tiles.iter().filter_map(|t| t.color());
+            let first_color = colors.next();
+            if !(3..=13).contains(&len) || colors.any(|c| Some(c) != first_color) {
+                return false;
+            }
+            // Slot values run start..start+len-1, where 14 is played by a 1
+            (1..=15 - len).any(|start| {
+                meld.tiles.iter().enumerate().all(|(pos, tile)| {
+                    let value = (start + pos) as u8;
+                    tile.number().is_none_or(|n| n == if value == 14 { 1 } else { value })
+                })
+            })
+        }
+    }
+}
+
+/// Find a winning arrangement of a 14-tile hand, if it has one.
+///
+/// The hand is given as physical tiles and normalized first.
+pub fn winning_hand(hand: &Hand, rules: &OkeyRules) -> Option<OkeyWin> {
+    let hand = rules.normalize_hand(hand);
+    if hand.len() != WINNING_HAND_SIZE {
+        return None;
+    }
+
+    let (unmatched, arrangement) = arrange_normalized(&hand);
+    (unmatched == 0).then(|| arrangement.into_win())
+}
+
+/// Arrange a hand as close to winning as it gets: the fewest tiles left
+/// outside melds or pairs, and the melds or pairs that leave them.
+///
+/// The hand is given as physical tiles and normalized first.
+pub fn arrange_hand(hand: &Hand, rules: &OkeyRules) -> (usize, OkeyArrangement) {
+    arrange_normalized(&rules.normalize_hand(hand))
+}
+
+/// `arrange_hand` for a hand that is already normalized. Melds are preferred;
+/// pairs are used only when they leave fewer tiles over.
+fn arrange_normalized(hand: &Hand) -> (usize, OkeyArrangement) {
+    let (meld_unmatched, melds) = best_arrangement(hand);
+    if meld_unmatched == 0 {
+        return (0, OkeyArrangement::Melds(melds));
+    }
+    let (pair_unmatched, pairs) = best_pairs(hand);
+    if pair_unmatched < meld_unmatched {
+        (pair_unmatched, OkeyArrangement::Pairs(pairs))
+    } else {
+        (meld_unmatched, OkeyArrangement::Melds(melds))
+    }
+}
+
+/// Choose the discard from a 15-tile hand that leaves the fewest tiles
+/// outside melds (or pairs), preferring to keep wilds and low tiles.
+///
+/// The hand is given as physical tiles and normalized first.
+pub fn best_discard(hand: &Hand, rules: &OkeyRules) -> Result<OkeyDiscard, String> {
+    let hand = rules.normalize_hand(hand);
+    let size = hand.len();
+    if size != WINNING_HAND_SIZE + 1 {
+        return Err(format!(
+            "Okey discard needs {} tiles, got {}",
+            WINNING_HAND_SIZE + 1,
+            size
+        ));
+    }
+
+    let mut best: Option<OkeyDiscard> = None;
+    let candidates: Vec<Tile> = hand.iter().map(|(tile, _)| *tile).collect();
+    for discard in candidates {
+        let mut remaining = hand.clone();
+        remaining.remove(&discard);
+
+        let (unmatched, arrangement) = arrange_normalized(&remaining);
+
+        let better = match &best {
+            None => true,
+            Some(current) => {
+                (unmatched, discard.is_wild(), std::cmp::Reverse(discard.number()))
+                    < (
+                        current.unmatched,
+                        current.discard.is_wild(),
+                        std::cmp::Reverse(current.discard.number()),
+                    )
+            }
+        };
+        if better {
+            best = Some(OkeyDiscard {
+                discard,
+                unmatched,
+                arrangement,
+            });
+        }
+    }
+
+    best.ok_or_else(|| "Okey discard needs a non-empty hand".to_string())
+}
+
+/// Arrange a normalized hand into melds, leaving as few tiles out as possible.
+/// Returns the number of tiles left out and the melds used.
+fn best_arrangement(hand: &Hand) -> (usize, Vec<Meld>) {
+    let candidates = generate_okey_melds(hand);
+    let mut remaining = hand.clone();
+    let mut chosen = Vec::new();
+    let mut best = (hand.len() + 1, Vec::new());
+    arrange(&mut remaining, &candidates, &mut chosen, 0, &mut best);
+    best
+}
+
+/// Backtracking over the lowest remaining numbered tile: either it joins one of
+/// the candidate melds, or it is left out
+fn arrange(
+    remaining: &mut Hand,
+    candidates: &[Meld],
+    chosen: &mut Vec<Meld>,
+    left_out: usize,
+    best: &mut (usize, Vec<Meld>),
+) {
+    if left_out >= best.0 {
+        return;
+    }
+
+    let Some(tile) = remaining.iter().map(|(t, _)| *t).find(|t| !t.is_wild()) else {
+        // Only wilds remain; they count as left out
+        let total = left_out + remaining.len();
+        if total < best.0 {
+            *best = (total, chosen.clone());
+        }
+        return;
+    };
+
+    for meld in candidates.iter().filter(|m| m.tiles.contains(&tile)) {
+        if !fits(remaining, meld) {
+            continue;
+        }
+        for t in &meld.tiles {
+            remaining.remove(t);
+        }
+        chosen.push(meld.clone());
+        arrange(remaining, candidates, chosen, left_out, best);
+        chosen.pop();
+        for t in &meld.tiles {
+            remaining.add(*t);
+        }
+    }
+
+    remaining.remove(&tile);
+    arrange(remaining, candidates, chosen, left_out + 1, best);
+    remaining.add(tile);
+}
+
+/// Check that the hand holds every tile of the meld
+fn fits(hand: &Hand, meld: &Meld) -> bool {
+    meld.tiles
+        .iter()
+        .all(|t| hand.count(t) as usize >= meld.tiles.iter().filter(|u| *u == t).count())
+}
+
+/// Pair up a normalized hand: identical tiles pair, and wilds pair with
+/// anything. Returns the number of tiles left unpaired and the pairs.
+fn best_pairs(hand: &Hand) -> (usize, Vec<(Tile, Tile)>) {
+    let mut pairs = Vec::new();
+    let mut singles = Vec::new();
+    let mut wilds = 0;
+    for (tile, &count) in hand.iter() {
+        if tile.is_wild() {
+            wilds += count as usize;
+            continue;
+        }
+        for _ in 0..count / 2 {
+            pairs.push((*tile, *tile));
+        }
+        if count % 2 == 1 {
+            singles.push(*tile);
+        }
+    }
+
+    let mut unpaired = 0;
+    for single in singles {
+        if wilds > 0 {
+            wilds -= 1;
+            pairs.push((single, Tile::wild()));
+        } else {
+            unpaired += 1;
+        }
+    }
+    for _ in 0..wilds / 2 {
+        pairs.push((Tile::wild(), Tile::wild()));
+    }
+    (unpaired + wilds % 2, pairs)
+}
+
+/// Generate every Okey meld a normalized hand could form
+fn generate_okey_melds(hand: &Hand) -> Vec<Meld> {
+    let wilds: usize = hand
+        .iter()
+        .filter(|(t, _)| t.is_wild())
+        .map(|(_, &count)| count as usize)
+        .sum();
+    let mut melds = Vec::new();
+
+    // Groups: any set of present colors, topped up with wilds
+    for number in 1..=13 {
+        let colors: Vec<u8> = (0..4).filter(|&c| hand.count(&Tile::new(c, number)) > 0).collect();
+        for mask in 0u8..(1 << colors.len()) {
+            let real = mask.count_ones() as usize;
+            for wild_count in 0..=wilds {
+                if !(3..=4).contains(&(real + wild_count)) || real == 0 {
+                    continue;
+                }
+                let mut tiles: VecDeque<Tile> = colors
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| mask & (1 << i) != 0)
+                    .map(|(_, &c)| Tile::new(c, number))
+                    .collect();
+                tiles.extend(std::iter::repeat_n(Tile::wild(), wild_count));
+                melds.push(Meld::new(MeldType::Group, tiles));
+            }
+        }
+    }
+
+    // Runs: every window of slots, possibly wrapping 13 to 1, with wilds in the gaps.
+    // Built directly, as `Meld::new` would reorder a wrapped run.
+    for color in 0..4 {
+        for start in 1..=12u8 {
+            let mut tiles = VecDeque::new();
+            let mut missing = 0;
+            for value in start..=14 {
+                let tile = Tile::new(color, if value == 14 { 1 } else { value });
+                if hand.count(&tile) > 0 {
+                    tiles.push_back(tile);
+                } else {
+                    missing += 1;
+                    tiles.push_back(Tile::wild());
+                }
+                if missing > wilds || tiles.len() > 13 {
+                    break;
+                }
+                if tiles.len() >= 3 && tiles.iter().any(|t| !t.is_wild()) {
+                    melds.push(Meld {
+                        meld_type: MeldType::Run,
+                        tiles: tiles.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    melds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand_from(tiles: &str) -> Hand {
+        let mut hand = Hand::new();
+        for s in tiles.split_whitespace() {
+            hand.add(Tile::from_string(s).unwrap());
+        }
+        hand
+    }
+
+    #[test]
+    fn test_okey_tile_from_indicator() {
+        let rules = OkeyRules::new(Tile::new(0, 5)).unwrap();
+        assert_eq!(rules.okey_tile(), Tile::new(0, 6));
+
+        // 13 wraps to 1
+        let rules = OkeyRules::new(Tile::new(2, 13)).unwrap();
+        assert_eq!(rules.okey_tile(), Tile::new(2, 1));
+
+        assert!(OkeyRules::new(Tile::wild()).is_err());
+    }
+
+    #[test]
+    fn test_normalize_okey_and_false_jokers() {
+        let rules = OkeyRules::new(Tile::new(0, 5)).unwrap();
+        assert_eq!(rules.normalize(Tile::new(0, 6)), Tile::wild());
+        assert_eq!(rules.normalize(Tile::wild()), Tile::new(0, 6));
+        assert_eq!(rules.normalize(Tile::new(1, 6)), Tile::new(1, 6));
+
+        for tile in [Tile::new(0, 6), Tile::wild(), Tile::new(3, 2)] {
+            assert_eq!(rules.physical_tile(rules.normalize(tile)), tile);
+        }
+    }
+
+    #[test]
+    fn test_okey_run_wraps_to_one() {
+        let run = |tiles: &[Tile]| Meld {
+            meld_type: MeldType::Run,
+            tiles: tiles.iter().copied().collect(),
+        };
+        assert!(is_valid_okey_meld(&run(&[Tile::new(0, 12), Tile::new(0, 13), Tile::new(0, 1)])));
+        assert!(is_valid_okey_meld(&run(&[Tile::new(0, 12), Tile::wild(), Tile::new(0, 1)])));
+        // 1 only ends a wrapped run
+        assert!(!is_valid_okey_meld(&run(&[Tile::new(0, 13), Tile::new(0, 1), Tile::new(0, 2)])));
+        assert!(!is_valid_okey_meld(&run(&[Tile::new(0, 12), Tile::new(1, 13), Tile::new(0, 1)])));
+    }
+
+    #[test]
+    fn test_winning_hand_with_melds() {
+        // Indicator b9 makes b10 the okey; the false joker "w" plays as b10
+        let rules = OkeyRules::new(Tile::new(1, 9)).unwrap();
+        let hand = hand_from("r12 r13 r1 b7 y7 k7 y3 y4 y5 y6 b10 b11 b8 w");
+
+        let Some(OkeyWin::Melds(melds)) = winning_hand(&hand, &rules) else {
+            panic!("hand should win with melds");
+        };
+        assert_eq!(melds.iter().map(|m| m.tiles.len()).sum::<usize>(), WINNING_HAND_SIZE);
+        assert!(melds.iter().all(is_valid_okey_meld));
+    }
+
+    #[test]
+    fn test_winning_hand_seven_pairs() {
+        let rules = OkeyRules::new(Tile::new(3, 1)).unwrap();
+        // k2 is the okey and pairs with the lone r11
+        let hand = hand_from("r1 r1 b5 b5 y9 y9 k13 k13 r3 r3 b12 b12 r11 k2");
+
+        assert!(matches!(winning_hand(&hand, &rules), Some(OkeyWin::SevenPairs(pairs)) if pairs.len() == 7));
+    }
+
+    #[test]
+    fn test_not_winning_hand() {
+        let rules = OkeyRules::new(Tile::new(3, 1)).unwrap();
+        let hand = hand_from("r1 r2 r3 b5 b6 b7 y9 y10 y11 k4 k5 k6 r8 b13");
+        assert_eq!(winning_hand(&hand, &rules), None);
+        assert!(matches!(arrange_hand(&hand, &rules), (2, OkeyArrangement::Melds(melds)) if melds.len() == 4));
+
+        let short = hand_from("r1 r2 r3");
+        assert_eq!(winning_hand(&short, &rules), None);
+    }
+
+    #[test]
+    fn test_arrange_hand_near_seven_pairs() {
+        let rules = OkeyRules::new(Tile::new(3, 1)).unwrap();
+        let hand = hand_from("r1 r1 b5 b5 y9 y9 k13 k13 r3 r3 b12 b12 r11 y6");
+
+        // Six pairs leave two tiles over, where melds would leave them all
+        let (unmatched, arrangement) = arrange_hand(&hand, &rules);
+        assert_eq!(unmatched, 2);
+        assert!(matches!(arrangement, OkeyArrangement::Pairs(pairs) if pairs.len() == 6));
+        assert_eq!(winning_hand(&hand, &rules), None);
+    }
+
+    #[test]
+    fn test_best_discard_completes_hand() {
+        let rules = OkeyRules::new(Tile::new(3, 1)).unwrap();
+        let hand = hand_from("r1 r2 r3 r4 b5 b6 b7 y9 y10 y11 r8 b8 k8 y8 y13");
+
+        let discard = best_discard(&hand, &rules).unwrap();
+        assert_eq!(discard.discard, Tile::new(2, 13));
+        assert!(discard.wins());
+        assert_eq!(discard.unmatched, 0);
+    }
+
+    #[test]
+    fn test_best_discard_keeps_wild() {
+        // k2 is the okey: it should never be thrown away when another tile will do
+        let rules = OkeyRules::new(Tile::new(3, 1)).unwrap();
+        let hand = hand_from("r1 r2 r3 b5 b6 b7 y9 y10 y11 y12 y13 r8 b8 k2 b13");
+
+        let discard = best_discard(&hand, &rules).unwrap();
+        assert!(!discard.discard.is_wild());
+        assert!(discard.wins());
+
+        assert!(best_discard(&hand_from("r1 r2"), &rules).is_err());
+    }
+
+    #[test]
+    fn test_best_discard_into_seven_pairs() {
+        let rules = OkeyRules::new(Tile::new(3, 1)).unwrap();
+        let hand = hand_from("r1 r1 b5 b5 y9 y9 k13 k13 r3 r3 b12 b12 r11 r11 y6");
+
+        let discard = best_discard(&hand, &rules).unwrap();
+        assert_eq!(discard.discard, Tile::new(2, 6));
+        assert_eq!(discard.unmatched, 0);
+        assert!(matches!(discard.win(), Some(OkeyWin::SevenPairs(pairs)) if pairs.len() == 7));
+    }
+}
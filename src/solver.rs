@@ -1,28 +1,59 @@
-use crate::{Hand, Meld, MeldType, Table, Tile};
+use crate::{Hand, Meld, MeldType, Table, Tile, TwistPower, WildAssignment};
+use std::cell::Cell;
 use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Cross-platform time tracker for timeout handling
-#[derive(Clone, Copy)]
-struct TimeTracker {
+#[derive(Clone)]
+pub(crate) struct TimeTracker {
     #[cfg(not(target_arch = "wasm32"))]
     start: std::time::Instant,
     #[cfg(target_arch = "wasm32")]
     start_ms: f64,
     limit_ms: u64,
+    /// Stop the search at the first play found, as if the time had run out
+    first_play: bool,
+    found: Cell<bool>,
 }
 
 impl TimeTracker {
-    fn new(limit_ms: u64) -> Self {
+    pub(crate) fn new(limit_ms: u64) -> Self {
         Self {
             #[cfg(not(target_arch = "wasm32"))]
             start: std::time::Instant::now(),
             #[cfg(target_arch = "wasm32")]
             start_ms: js_sys::Date::now(),
             limit_ms,
+            first_play: false,
+            found: Cell::new(false),
         }
     }
 
-    fn is_expired(&self) -> bool {
+    /// A tracker for query searches, which end at the first play found
+    pub(crate) fn until_first_play(limit_ms: u64) -> Self {
+        Self {
+            first_play: true,
+            ..Self::new(limit_ms)
+        }
+    }
+
+    /// Note that the search found a play; ends a first-play search
+    pub(crate) fn record_play(&self) {
+        if self.first_play {
+            self.found.set(true);
+        }
+    }
+
+    /// A first-play search found its play and stopped there
+    pub(crate) fn stopped_at_play(&self) -> bool {
+        self.found.get()
+    }
+
+    /// The search should stop: the time is up, or a first-play search has
+    /// found its play
+    pub(crate) fn is_expired(&self) -> bool {
+        if self.found.get() {
+            return true;
+        }
         #[cfg(not(target_arch = "wasm32"))]
         {
             self.start.elapsed() >= std::time::Duration::from_millis(self.limit_ms)
@@ -33,6 +64,15 @@ impl TimeTracker {
             (now - self.start_ms) >= self.limit_ms as f64
         }
     }
+
+    /// Milliseconds left before the limit, zero once expired
+    pub(crate) fn remaining_ms(&self) -> u64 {
+        #[cfg(not(target_arch = "wasm32"))]
+        let elapsed_ms = self.start.elapsed().as_millis() as u64;
+        #[cfg(target_arch = "wasm32")]
+        let elapsed_ms = (js_sys::Date::now() - self.start_ms).max(0.0) as u64;
+        self.limit_ms.saturating_sub(elapsed_ms)
+    }
 }
 
 /// Represents a solver move in the Rummikub game
@@ -40,10 +80,21 @@ impl TimeTracker {
 pub enum SolverMove {
     /// Pick up a meld from the table at the given index and add it to the player's hand
     PickUp(usize),
-    /// Play a meld from the player's hand onto the table
+    /// Play a meld from the player's hand onto the table.
+    /// Any wilds are played as the tiles given by `joker_assignments`.
     LayDown(Meld),
 }
 
+impl SolverMove {
+    /// The tiles the wilds of a laid-down meld are played as (none for a pickup)
+    pub fn joker_assignments(&self) -> Vec<WildAssignment> {
+        match self {
+            SolverMove::PickUp(_) => Vec::new(),
+            SolverMove::LayDown(meld) => meld.wild_assignments(),
+        }
+    }
+}
+
 /// Scoring strategy for evaluating the quality of a hand
 #[derive(Debug, Clone, Copy)]
 pub enum ScoringStrategy {
@@ -51,6 +102,30 @@ pub enum ScoringStrategy {
     MinimizeTiles,
     /// Minimize the point value of remaining tiles (sum of numbers)
     MinimizePoints,
+    /// Value what stays on the rack as well as what gets played: pairs,
+    /// near-runs and jokers count in favor of the rack, and one complete meld
+    /// may be held back for the next turn unless the player can go out
+    KeepOptions,
+}
+
+/// Why a tile left on the rack by `ScoringStrategy::KeepOptions` is worth keeping
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoldReason {
+    /// Part of a complete meld held back to play next turn
+    ReserveMeld,
+    /// A joker kept for later flexibility
+    Wild,
+    /// Same number as another rack tile of a different color (toward a group)
+    Pair,
+    /// Same color as a rack tile one or two numbers away (toward a run)
+    NearRun,
+}
+
+/// A tile left on the rack and the reason it was kept
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeldTile {
+    pub tile: Tile,
+    pub reason: HoldReason,
 }
 
 /// What tile a wild represents in a meld
@@ -60,6 +135,18 @@ enum RepresentedTile {
     Concrete(Tile),
     /// Either of two tiles (ambiguous: groups of 3)
     EitherOf(Tile, Tile),
+    /// Any of the listed tiles (ambiguous: groups of 3 holding two wilds)
+    OneOf(Vec<Tile>),
+}
+
+/// Obligation to play `count` distinct tiles out of `options`.
+///
+/// Arises from groups holding several wilds whose colors are not forced:
+/// in `7 r w w` the two wilds stand for two different colors out of b, y, k.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DistinctChoice {
+    options: Vec<Tile>,
+    count: u8,
 }
 
 /// Tracks wild replacement obligations when picking up melds from the table
@@ -69,6 +156,49 @@ struct WildDebt {
     concrete: HashMap<Tile, u8>,
     /// Play at least one of the pair (from groups of 3)
     either_or: Vec<(Tile, Tile)>,
+    /// Play several distinct tiles of a set (from groups with multiple wilds)
+    distinct: Vec<DistinctChoice>,
+    /// Melds whose wilds may not be retrieved: all their tiles must be laid down together
+    keep_intact: Vec<Meld>,
+    /// Number of wilds retrieved from the picked-up melds
+    retrieved_wilds: u8,
+    /// Tiles picked up from the table, used to tell them apart from hand tiles
+    table_tiles: HashMap<Tile, u8>,
+    /// House rules the debt is checked against
+    rules: HouseRules,
+}
+
+/// House rules for retrieving wilds (jokers) from melds on the table, and for
+/// the shape of runs.
+///
+/// The default is the solver's built-in behavior: a wild may be taken from any
+/// meld as long as the tile it represents is played this turn, from hand or
+/// from the table, and runs stop at 13.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HouseRules {
+    /// A retrieved wild must be played again this turn in a meld that holds
+    /// at least one tile from the player's hand
+    pub replay_with_hand_tile: bool,
+    /// Wilds may not be taken out of groups of 3 (the group may still be
+    /// extended, as long as its tiles stay together)
+    pub no_retrieval_from_groups_of_three: bool,
+    /// The tile that replaces a wild must come from the player's hand,
+    /// not from another meld on the table
+    pub replacement_from_hand_only: bool,
+    /// Runs may wrap around from 13 back to 1 (12-13-1-2)
+    pub wrap_around_runs: bool,
+}
+
+/// Limits the player puts on the search on top of the game rules, e.g. a
+/// table meld that is physically out of reach
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayConstraints {
+    /// Hand tiles that must stay on the rack
+    pub locked: Hand,
+    /// Hand tiles that must be laid down this turn
+    pub must_play: Hand,
+    /// Table melds (by index) that must not be picked up or changed
+    pub frozen_melds: Vec<usize>,
 }
 
 /// Detailed result from the solver including metadata about the search
@@ -84,6 +214,31 @@ pub struct SolverResult {
     pub initial_quality: i32,
     /// Final hand quality after applying the solution
     pub final_quality: i32,
+    /// Tiles kept on the rack on purpose; only filled in by
+    /// `ScoringStrategy::KeepOptions`
+    pub held_back: Vec<HeldTile>,
+}
+
+/// How a solver run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveStatus {
+    /// A play was found (possibly not the best, if the search timed out)
+    Solved,
+    /// The search covered every depth and no legal play exists
+    Infeasible,
+    /// The time limit ran out before any play was found
+    TimedOut,
+}
+
+impl SolverResult {
+    /// Tell a proven absence of plays apart from a search that was cut off
+    pub fn status(&self) -> SolveStatus {
+        match (&self.moves, self.search_completed) {
+            (Some(_), _) => SolveStatus::Solved,
+            (None, true) => SolveStatus::Infeasible,
+            (None, false) => SolveStatus::TimedOut,
+        }
+    }
 }
 
 // ============================================================================
@@ -93,6 +248,8 @@ pub struct SolverResult {
 /// Declarative description of how a meld was transformed or created.
 /// These moves describe transformations in terms humans can understand,
 /// rather than the internal "destroy and rebuild" approach of SolverMove.
+/// Wilds in the melds a move leaves on the table stand for the tiles given by
+/// `HumanMove::joker_assignments`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum HumanMove {
     /// Play a meld entirely from hand (no table tiles involved)
@@ -132,6 +289,26 @@ pub enum HumanMove {
         result: Meld,
     },
 
+    /// Move one tile out of a meld that stays valid without it, either onto
+    /// another meld or to start a new one, adding hand tiles at the destination
+    MoveTile {
+        tile: Tile,
+        from: Meld,
+        remaining: Meld,
+        /// The meld the tile joins, or None when it starts a new meld
+        to: Option<Meld>,
+        added_tiles: Vec<Tile>,
+        result: Meld,
+    },
+
+    /// Split a run and play hand tiles into the pieces, e.g. a second R3
+    /// splitting R1-R5 into R1-R3 and R3-R5
+    InsertAndSplit {
+        original: Meld,
+        inserted: Vec<Tile>,
+        parts: Vec<Meld>,
+    },
+
     /// Complex rearrangement that doesn't fit other patterns
     Rearrange {
         consumed: Vec<Meld>,
@@ -140,6 +317,67 @@ pub enum HumanMove {
     },
 }
 
+impl HumanMove {
+    /// The melds this move leaves on the table
+    pub fn laid_melds(&self) -> Vec<&Meld> {
+        match self {
+            HumanMove::PlayFromHand(meld) => vec![meld],
+            HumanMove::ExtendMeld { result, .. }
+            | HumanMove::JoinMelds { result, .. }
+            | HumanMove::SwapWild { result, .. } => vec![result],
+            HumanMove::TakeFromMeld { remaining, .. } => vec![remaining],
+            HumanMove::SplitMeld { parts, .. } | HumanMove::InsertAndSplit { parts, .. } => parts.iter().collect(),
+            HumanMove::MoveTile { remaining, result, .. } => vec![remaining, result],
+            HumanMove::Rearrange { produced, .. } => produced.iter().collect(),
+        }
+    }
+
+    /// Each meld this move leaves on the table with the tiles its wilds are
+    /// played as; melds without wilds are left out
+    pub fn joker_assignments(&self) -> Vec<(&Meld, Vec<WildAssignment>)> {
+        self.laid_melds()
+            .into_iter()
+            .map(|meld| (meld, meld.wild_assignments()))
+            .filter(|(_, assignments)| !assignments.is_empty())
+            .collect()
+    }
+}
+
+/// One physical step at the table. Melds are referred to by slot: slots
+/// `0..n` are the original table melds by index, and each split or newly
+/// started meld takes the next free slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TileAction {
+    /// Split the run in `slot` after its first `after` tiles; the rest
+    /// becomes `new_slot`
+    Split { slot: usize, after: usize, new_slot: usize },
+    /// Move a tile from one meld to another (`to` may be a new slot)
+    MoveTile { tile: Tile, from: usize, to: usize },
+    /// Place a tile from the rack into a meld (`to` may be a new slot)
+    PlaceFromRack { tile: Tile, to: usize },
+    /// Take a tile off the table back onto the rack (a retrieved wild)
+    TakeToRack { tile: Tile, from: usize },
+}
+
+/// An ordered list of physical steps that turns the original table into
+/// the solver's final one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManipulationPlan {
+    pub actions: Vec<TileAction>,
+    /// The slot holding each laid-down meld at the end, in solver order
+    pub final_slots: Vec<usize>,
+}
+
+impl ManipulationPlan {
+    /// Tiles moved between melds or placed from the rack
+    pub fn tile_moves(&self) -> usize {
+        self.actions
+            .iter()
+            .filter(|a| matches!(a, TileAction::MoveTile { .. } | TileAction::PlaceFromRack { .. }))
+            .count()
+    }
+}
+
 /// Tracks where a tile came from
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum TileSource {
@@ -189,8 +427,117 @@ impl ScoringStrategy {
                     .sum();
                 -points
             }
+            Self::KeepOptions => rack_options_value(hand),
+        }
+    }
+}
+
+/// Rack values for `ScoringStrategy::KeepOptions`, in points where each tile
+/// left on the rack costs `TILE_COST`
+const TILE_COST: i32 = 10;
+const GO_OUT_BONUS: i32 = 100;
+const RESERVE_MELD_BONUS: i32 = 25;
+const WILD_BONUS: i32 = 4;
+const PAIR_BONUS: i32 = 3;
+const NEAR_RUN_BONUS: i32 = 3;
+
+/// Score a rack by its tile count and the options it leaves open.
+///
+/// Called for every terminal state of the search, so the meld check is a
+/// rough one over number bitmasks rather than full meld generation.
+fn rack_options_value(hand: &Hand) -> i32 {
+    let tiles: i32 = hand.iter().map(|(_, &c)| c as i32).sum();
+    if tiles == 0 {
+        return GO_OUT_BONUS;
+    }
+
+    // Bit n of numbers_by_color[c] is set when color c holds number n,
+    // bit c of colors_by_number[n] when number n comes in color c
+    let mut numbers_by_color = [0u16; 4];
+    let mut colors_by_number = [0u8; 14];
+    let mut wilds = 0;
+    let mut jokers = 0;
+    for (tile, &count) in hand.iter() {
+        match (tile.color(), tile.number()) {
+            (Some(color), Some(number)) => {
+                numbers_by_color[color as usize] |= 1 << number;
+                colors_by_number[number as usize] |= 1 << color;
+            }
+            _ => {
+                wilds += count as i32;
+                if tile.twist_power().is_none() {
+                    jokers += count as u32;
+                }
+            }
+        }
+    }
+
+    let mut value = -TILE_COST * tiles + WILD_BONUS * wilds;
+    for colors in colors_by_number {
+        let count = colors.count_ones() as i32;
+        if count >= 2 {
+            value += PAIR_BONUS * (count - 1);
+        }
+    }
+    for numbers in numbers_by_color {
+        value += NEAR_RUN_BONUS * near_run_links(numbers) as i32;
+    }
+
+    let holds_group = colors_by_number
+        .iter()
+        .any(|colors| *colors != 0 && colors.count_ones() + jokers >= 3);
+    let holds_run = numbers_by_color.iter().any(|numbers| {
+        (1..=11).any(|start| {
+            let window = (numbers >> start) & 0b111;
+            window != 0 && 3 - window.count_ones() <= jokers
+        })
+    });
+    if holds_group || holds_run {
+        value += RESERVE_MELD_BONUS;
+    }
+    value
+}
+
+/// Count the numbers in a bitmask that have another number one or two above them
+fn near_run_links(numbers: u16) -> u32 {
+    (numbers & ((numbers >> 1) | (numbers >> 2))).count_ones()
+}
+
+/// Explain the tiles left on a rack: the longest meld still in it is the
+/// reserve, and the other tiles are kept for the first reason that applies.
+/// Tiles with no reason to keep them are left out.
+pub fn explain_held_tiles(rack: &Hand, rules: &HouseRules) -> Vec<HeldTile> {
+    let mut rest = rack.clone();
+    let mut held = Vec::new();
+
+    if let Some(reserve) = generate_all_valid_melds(rack, rules)
+        .into_iter()
+        .max_by_key(|meld| meld.tiles.len())
+    {
+        for tile in &reserve.tiles {
+            rest.remove(tile);
+            held.push(HeldTile { tile: *tile, reason: HoldReason::ReserveMeld });
+        }
+    }
+
+    let naturals: Vec<Tile> = rack.iter().map(|(tile, _)| *tile).filter(|t| !t.is_wild()).collect();
+    for (tile, &count) in rest.iter() {
+        let reason = if tile.is_wild() {
+            Some(HoldReason::Wild)
+        } else if naturals.iter().any(|t| t.number() == tile.number() && t.color() != tile.color()) {
+            Some(HoldReason::Pair)
+        } else if naturals.iter().any(|t| {
+            t.color() == tile.color() && (1..=2).contains(&t.number().unwrap_or(0).abs_diff(tile.number().unwrap_or(0)))
+        }) {
+            Some(HoldReason::NearRun)
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            held.extend(std::iter::repeat_n(HeldTile { tile: *tile, reason }, count as usize));
         }
     }
+    held
 }
 
 /// Find the best sequence of moves to play tiles from hand, potentially manipulating the table.
@@ -224,22 +571,128 @@ pub fn find_best_moves_with_strategy(
     hand: &mut Hand,
     max_ms: u64,
     strategy: ScoringStrategy,
+) -> SolverResult {
+    find_best_moves_with_rules(table, hand, max_ms, strategy, &HouseRules::default())
+}
+
+/// Find the best sequence of moves using a specific scoring strategy and
+/// house rules.
+///
+/// Same search as `find_best_moves_with_strategy`; the rules decide which
+/// picked-up wilds may be reused and how they must be paid for.
+pub fn find_best_moves_with_rules(
+    table: &mut Table,
+    hand: &mut Hand,
+    max_ms: u64,
+    strategy: ScoringStrategy,
+    rules: &HouseRules,
+) -> SolverResult {
+    find_best_moves_with_constraints(table, hand, max_ms, strategy, rules, &PlayConstraints::default())
+}
+
+/// Find the best sequence of moves under house rules and the player's own
+/// constraints.
+///
+/// Same search as `find_best_moves_with_rules`; locked tiles never leave the
+/// rack, every must-play tile is laid down, and frozen table melds are never
+/// picked up. Returns no moves if no play meets the constraints.
+pub fn find_best_moves_with_constraints(
+    table: &mut Table,
+    hand: &mut Hand,
+    max_ms: u64,
+    strategy: ScoringStrategy,
+    rules: &HouseRules,
+    constraints: &PlayConstraints,
 ) -> SolverResult {
     let quality = |h: &Hand| strategy.evaluate(h);
-    find_best_moves_internal(table, hand, max_ms, quality)
+    let timer = TimeTracker::new(max_ms);
+    let mut result = find_best_moves_internal(table, hand, timer, quality, rules, constraints);
+
+    if let (ScoringStrategy::KeepOptions, Some(moves)) = (strategy, &result.moves) {
+        let mut rack = hand.clone();
+        apply_moves(&mut table.clone(), &mut rack, moves);
+        result.held_back = explain_held_tiles(&rack, rules);
+    }
+    result
+}
+
+/// Query mode: search for any legal play that lays down `tile`, rather than
+/// the best play overall.
+///
+/// Only plays that lay a copy of `tile` down from the hand are accepted, and
+/// the search stops at the first one found; shallower searches run first,
+/// which favors plays that disturb the table least. Returns no moves if no
+/// such play exists, and counts the search as completed once a play is found.
+pub fn find_moves_playing(
+    table: &mut Table,
+    hand: &mut Hand,
+    tile: Tile,
+    max_ms: u64,
+    rules: &HouseRules,
+) -> SolverResult {
+    let mut constraints = PlayConstraints::default();
+    constraints.must_play.add(tile);
+    let timer = TimeTracker::until_first_play(max_ms);
+    find_best_moves_internal(table, hand, timer, |_: &Hand| 0, rules, &constraints)
+}
+
+/// Carry out a move sequence found for `table` and `hand`: picked-up melds
+/// go into the hand, then the laid-down melds come out of it onto the table
+pub fn apply_moves(table: &mut Table, hand: &mut Hand, moves: &[SolverMove]) {
+    // Pick-up indices refer to the table the moves were found for, so remove
+    // from the highest index down to keep the others in place
+    let mut picked: Vec<usize> = moves
+        .iter()
+        .filter_map(|m| match m {
+            SolverMove::PickUp(idx) => Some(*idx),
+            SolverMove::LayDown(_) => None,
+        })
+        .collect();
+    picked.sort_unstable_by(|a, b| b.cmp(a));
+    for idx in picked {
+        if let Some(meld) = table.remove_meld(idx) {
+            for tile in meld.tiles {
+                hand.add(tile);
+            }
+        }
+    }
+
+    for mov in moves {
+        if let SolverMove::LayDown(meld) = mov {
+            for tile in &meld.tiles {
+                hand.remove(tile);
+            }
+            table.add_meld(meld.clone());
+        }
+    }
+}
+
+/// Number of hand tiles a move sequence plays: the tiles laid down less the
+/// tiles picked up from `table`, the table the moves were found for
+pub fn tiles_played(table: &Table, moves: &[SolverMove]) -> usize {
+    let mut laid_down = 0;
+    let mut picked_up = 0;
+    for mov in moves {
+        match mov {
+            SolverMove::PickUp(idx) => picked_up += table.melds().get(*idx).map_or(0, |m| m.tiles.len()),
+            SolverMove::LayDown(meld) => laid_down += meld.tiles.len(),
+        }
+    }
+    laid_down.saturating_sub(picked_up)
 }
 
 /// Internal implementation of find_best_moves that accepts a custom quality function.
 fn find_best_moves_internal<F>(
     table: &mut Table,
     hand: &mut Hand,
-    max_ms: u64,
+    timer: TimeTracker,
     quality: F,
+    rules: &HouseRules,
+    constraints: &PlayConstraints,
 ) -> SolverResult
 where
     F: Fn(&Hand) -> i32 + Copy,
 {
-    let timer = TimeTracker::new(max_ms);
     let original_hand = hand.clone();
     let original_table = table.clone();
 
@@ -250,7 +703,9 @@ where
     let mut depth_reached = 0;
 
     // BFS: Try depth 0 (direct play), then 1, 2, 3, etc.
-    let max_depth = table.len();
+    // Frozen melds are never picked up, so they do not add depth
+    let frozen = (0..table.len()).filter(|idx| constraints.frozen_melds.contains(idx)).count();
+    let max_depth = table.len() - frozen;
 
     for depth in 0..=max_depth {
         // Check time limit before starting each depth
@@ -267,13 +722,15 @@ where
             &original_hand,
             depth,
             quality,
+            rules,
+            constraints,
             &timer,
             &mut best_solution,
         );
     }
 
-    // Determine if search completed
-    let search_completed = !timer.is_expired() && depth_reached == max_depth;
+    // Determine if search completed; a first-play search is done once it has its play
+    let search_completed = timer.stopped_at_play() || (!timer.is_expired() && depth_reached == max_depth);
 
     // Calculate final quality
     let final_quality = if let Some((ref moves, _)) = best_solution {
@@ -307,16 +764,20 @@ where
         depth_reached,
         initial_quality,
         final_quality,
+        held_back: Vec::new(),
     }
 }
 
 /// Try all combinations of removing 'count' melds from the table and update best solution
+#[allow(clippy::too_many_arguments)]
 fn try_all_combinations_at_depth<F>(
     table: &mut Table,
     hand: &mut Hand,
     original_hand: &Hand,
     depth: usize,
     quality: F,
+    rules: &HouseRules,
+    constraints: &PlayConstraints,
     timer: &TimeTracker,
     best_solution: &mut Option<(Vec<SolverMove>, i32)>,
 )
@@ -329,7 +790,7 @@ where
     // No wild debt since we're not picking up any melds
     if depth == 0 {
         let empty_debt = WildDebt::default();
-        if let Some(melds) = find_best_melds(hand, quality, original_hand, timer, &empty_debt) {
+        if let Some(melds) = find_best_melds(hand, quality, original_hand, timer, &empty_debt, rules, constraints) {
             let moves: Vec<SolverMove> = melds
                 .iter()
                 .map(|meld| SolverMove::LayDown(meld.clone()))
@@ -345,7 +806,7 @@ where
             let score = quality(&temp_hand);
 
             // Update best solution if this is better
-            if best_solution.as_ref().map_or(true, |(_, best_score)| score > *best_score) {
+            if best_solution.as_ref().is_none_or(|(_, best_score)| score > *best_score) {
                 *best_solution = Some((moves, score));
             }
         }
@@ -369,8 +830,11 @@ where
             return;
         }
 
-        // Try this combination and update best solution if better
-        try_meld_combination(table, hand, original_hand, &indices, quality, timer, best_solution);
+        // Try this combination and update best solution if better,
+        // unless it picks up a meld the player may not touch
+        if !indices.iter().any(|idx| constraints.frozen_melds.contains(idx)) {
+            try_meld_combination(table, hand, original_hand, &indices, quality, rules, constraints, timer, best_solution);
+        }
 
         // Generate next combination
         if !next_combination(&mut indices, table_size) {
@@ -380,12 +844,15 @@ where
 }
 
 /// Try removing the melds at the given indices and update best solution if better
+#[allow(clippy::too_many_arguments)]
 fn try_meld_combination<F>(
     table: &mut Table,
     hand: &mut Hand,
     original_hand: &Hand,
     indices: &[usize],
     quality: F,
+    rules: &HouseRules,
+    constraints: &PlayConstraints,
     timer: &TimeTracker,
     best_solution: &mut Option<(Vec<SolverMove>, i32)>,
 )
@@ -409,10 +876,20 @@ where
 
     // Compute wild debts from the removed melds
     // Any wilds in these melds require replacement tiles to be played
-    let wild_debt = compute_wild_debts(&removed_melds);
+    let wild_debt = compute_wild_debts(&removed_melds, rules);
+
+    // Skip the search if the debts cannot be paid from the tiles available:
+    // the whole hand normally, only the original hand if replacements must come from it
+    let payment_pool = if rules.replacement_from_hand_only { original_hand } else { &*hand };
+    let payable = wild_debt_payable_from(&wild_debt, payment_pool);
 
     // Try to find melds from the new hand
-    if let Some(melds) = find_best_melds(hand, quality, original_hand, timer, &wild_debt) {
+    let found = if payable {
+        find_best_melds(hand, quality, original_hand, timer, &wild_debt, rules, constraints)
+    } else {
+        None
+    };
+    if let Some(melds) = found {
         // Build the move sequence
         let mut moves = Vec::new();
 
@@ -436,7 +913,7 @@ where
         let score = quality(&temp_hand);
 
         // Update best solution if this is better
-        if best_solution.as_ref().map_or(true, |(_, best_score)| score > *best_score) {
+        if best_solution.as_ref().is_none_or(|(_, best_score)| score > *best_score) {
             *best_solution = Some((moves, score));
         }
     }
@@ -457,8 +934,8 @@ fn generate_combination(
     if k == 0 {
         return true;
     }
-    for i in 0..k {
-        combo[i] = start + i;
+    for (i, slot) in combo.iter_mut().enumerate().take(k) {
+        *slot = start + i;
     }
     true
 }
@@ -496,12 +973,18 @@ fn next_combination(combo: &mut [usize], n: usize) -> bool {
 ///
 /// The wild_debt parameter specifies tiles that MUST be played in the melds
 /// to satisfy wild replacement constraints from picked-up table melds.
+///
+/// The constraints' locked tiles (up to the copies in hand_to_beat) are left
+/// out of the search, and every must-play tile has to be laid down.
+#[allow(clippy::too_many_arguments)]
 fn find_best_melds<F>(
     hand: &mut Hand,
     quality: F,
     hand_to_beat: &Hand,
     timer: &TimeTracker,
     wild_debt: &WildDebt,
+    rules: &HouseRules,
+    constraints: &PlayConstraints,
 ) -> Option<Vec<Meld>>
 where
     F: Fn(&Hand) -> i32,
@@ -509,8 +992,31 @@ where
     // Save the original hand state to ensure we restore it
     let original_hand = hand.clone();
 
+    // Locked tiles stay on the rack: take them out of the search and out of
+    // the hand to beat, and put them back when scoring a rack
+    let kept: Vec<Tile> = constraints
+        .locked
+        .iter()
+        .flat_map(|(tile, &count)| std::iter::repeat_n(*tile, count.min(hand_to_beat.count(tile)) as usize))
+        .collect();
+    let mut to_beat = hand_to_beat.clone();
+    for tile in &kept {
+        hand.remove(tile);
+        to_beat.remove(tile);
+    }
+    let quality = |remaining: &Hand| {
+        if kept.is_empty() {
+            return quality(remaining);
+        }
+        let mut rack = remaining.clone();
+        for tile in &kept {
+            rack.add(*tile);
+        }
+        quality(&rack)
+    };
+
     // Step 1: Generate all possible melds
-    let all_possible_melds = generate_all_valid_melds(hand);
+    let all_possible_melds = generate_all_valid_melds(hand, rules);
 
     // Step 2: Build tile -> meld indices mapping
     let tile_to_meld_indices = build_tile_index(&all_possible_melds);
@@ -528,9 +1034,10 @@ where
         &mut active_melds,
         &mut invalid_melds,
         &quality,
-        hand_to_beat,
+        &to_beat,
         timer,
         wild_debt,
+        constraints,
         &mut best,
     );
 
@@ -544,12 +1051,12 @@ where
 }
 
 /// Generate all valid melds that could potentially be formed from the hand
-fn generate_all_valid_melds(hand: &Hand) -> Vec<Meld> {
+fn generate_all_valid_melds(hand: &Hand, rules: &HouseRules) -> Vec<Meld> {
     let mut melds = Vec::new();
 
     // Generate runs for each color
     for color in 0..4 {
-        generate_runs_for_color(hand, color, &mut melds);
+        generate_runs_for_color(hand, color, rules.wrap_around_runs, &mut melds);
     }
 
     // Generate groups for each number
@@ -557,33 +1064,73 @@ fn generate_all_valid_melds(hand: &Hand) -> Vec<Meld> {
         generate_groups_for_number(hand, number, &mut melds);
     }
 
+    generate_twist_melds(hand, &mut melds);
+
     melds
 }
 
-/// Generate all possible runs for a specific color
-fn generate_runs_for_color(hand: &Hand, color: u8, melds: &mut Vec<Meld>) {
-    let num_wildcards = hand.count(&Tile::wild());
+/// Generate all possible runs for a specific color, including runs that wrap
+/// from 13 back to 1 when `wrap_around` is set
+pub(crate) fn generate_runs_for_color(hand: &Hand, color: u8, wrap_around: bool, melds: &mut Vec<Meld>) {
+    let wilds = wilds_for_color(hand, color);
+    let num_wildcards: u8 = wilds.iter().map(|(_, count)| count).sum();
 
     // Try all possible starting positions and lengths
-    for start in 1..=11 {
+    let last_start = if wrap_around { 13 } else { 11 };
+    for start in 1..=last_start {
         // Maximum run length from this starting position
-        let max_len = 14 - start;
+        let max_len = if wrap_around { 13 } else { 14 - start };
 
         // Try all lengths >= 3
         for length in 3..=max_len {
+            // A full 13-tile wrapped run holds the same tiles from every start
+            if wrap_around && length == 13 && start != 1 {
+                continue;
+            }
+
             // Generate all possible wildcard placement patterns
             let wildcard_patterns = generate_wildcard_patterns(length, num_wildcards);
 
             for pattern in wildcard_patterns {
                 if can_form_run(hand, color, start, length, &pattern) {
-                    let meld = build_run(color, start, length, pattern);
-                    melds.push(meld);
+                    // One meld per choice of which jokers fill the wild slots
+                    for selection in wild_selections(&wilds, pattern.len()) {
+                        melds.push(build_run(color, start, length, &pattern, &selection));
+                    }
                 }
             }
         }
     }
 }
 
+/// Wild tiles in the hand that can stand for tiles of the given color, with their counts
+fn wilds_for_color(hand: &Hand, color: u8) -> Vec<(Tile, u8)> {
+    hand.iter()
+        .filter(|(tile, _)| tile.can_represent(Tile::new(color, 1)))
+        .map(|(tile, &count)| (*tile, count))
+        .collect()
+}
+
+/// All ways to pick `count` wild tiles out of the available joker kinds.
+///
+/// Jokers of the same kind are interchangeable, so each selection is a
+/// multiset, listed in joker order.
+fn wild_selections(available: &[(Tile, u8)], count: usize) -> Vec<Vec<Tile>> {
+    let Some(((tile, tile_count), rest)) = available.split_first() else {
+        return if count == 0 { vec![Vec::new()] } else { Vec::new() };
+    };
+
+    let mut selections = Vec::new();
+    for taken in 0..=(*tile_count as usize).min(count) {
+        for mut tail in wild_selections(rest, count - taken) {
+            let mut selection = vec![*tile; taken];
+            selection.append(&mut tail);
+            selections.push(selection);
+        }
+    }
+    selections
+}
+
 /// Generate all possible positions where wildcards could be placed
 fn generate_wildcard_patterns(length: u8, available_wilds: u8) -> Vec<Vec<u8>> {
     let mut patterns = vec![Vec::new()]; // Start with empty pattern (no wildcards)
@@ -619,7 +1166,8 @@ fn can_form_run(
     wild_positions: &[u8],
 ) -> bool {
     let wilds_needed = wild_positions.len();
-    if hand.count(&Tile::wild()) < wilds_needed as u8 {
+    let wilds_available: u8 = wilds_for_color(hand, color).iter().map(|(_, count)| count).sum();
+    if wilds_available < wilds_needed as u8 {
         return false;
     }
 
@@ -627,7 +1175,7 @@ fn can_form_run(
     for i in 0..length {
         if !wild_positions.contains(&i) {
             // Need actual tile
-            let tile = Tile::new(color, start + i);
+            let tile = Tile::new(color, run_slot_number(start + i));
             if hand.count(&tile) == 0 {
                 return false;
             }
@@ -637,22 +1185,33 @@ fn can_form_run(
     true
 }
 
-/// Build a run meld
-fn build_run(color: u8, start: u8, length: u8, wild_positions: Vec<u8>) -> Meld {
+/// Build a run meld, filling the wild positions with the given wild tiles in order
+fn build_run(color: u8, start: u8, length: u8, wild_positions: &[u8], wild_tiles: &[Tile]) -> Meld {
     let mut tiles = VecDeque::new();
+    let mut wilds = wild_tiles.iter();
     for i in 0..length {
         if wild_positions.contains(&i) {
-            tiles.push_back(Tile::wild());
+            tiles.push_back(*wilds.next().unwrap_or(&Tile::wild()));
         } else {
-            tiles.push_back(Tile::new(color, start + i));
+            tiles.push_back(Tile::new(color, run_slot_number(start + i)));
         }
     }
     Meld::new(MeldType::Run, tiles)
 }
 
+/// The number in a run slot counted on from 1, wrapping from 13 back to 1
+fn run_slot_number(slot: u8) -> u8 {
+    (slot - 1) % 13 + 1
+}
+
 /// Generate all possible groups for a specific number
-fn generate_groups_for_number(hand: &Hand, number: u8, melds: &mut Vec<Meld>) {
-    let num_wildcards = hand.count(&Tile::wild());
+pub(crate) fn generate_groups_for_number(hand: &Hand, number: u8, melds: &mut Vec<Meld>) {
+    let wilds: Vec<(Tile, u8)> = hand
+        .iter()
+        .filter(|(tile, _)| tile.is_wild() && tile.twist_power().is_none())
+        .map(|(tile, &count)| (*tile, count))
+        .collect();
+    let num_wildcards: u8 = wilds.iter().map(|(_, count)| count).sum();
 
     // Count available tiles of this number for each color
     let mut available_colors = Vec::new();
@@ -671,37 +1230,46 @@ fn generate_groups_for_number(hand: &Hand, number: u8, melds: &mut Vec<Meld>) {
     // Generate all valid combinations of colors + wildcards
     // Groups can be size 3 or 4
     for group_size in 3..=4 {
-        let wilds_needed = if group_size > available_colors.len() {
-            group_size - available_colors.len()
-        } else {
-            0
-        };
+        let wilds_needed = group_size - available_colors.len().min(group_size);
 
         if wilds_needed > num_wildcards as usize {
             continue;
         }
 
-        // Generate all subsets of available colors of the right size
-        generate_color_combinations(&available_colors, group_size - wilds_needed, wilds_needed, number, melds);
+        // Generate all subsets of available colors of the right size,
+        // paired with every choice of jokers for the wild slots
+        let wild_sets = wild_selections(&wilds, wilds_needed);
+        generate_color_combinations(&available_colors, group_size - wilds_needed, &wild_sets, number, melds);
+    }
+}
+
+/// Check that a set of wilds can fill the colors missing from a group:
+/// color-restricted jokers need their own color free, and no two may share it
+fn wilds_fit_group(colors: &[u8], wilds: &[Tile]) -> bool {
+    let mut filled = colors.to_vec();
+    for color in wilds.iter().filter_map(|t| t.wild_color()) {
+        if filled.contains(&color) {
+            return false;
+        }
+        filled.push(color);
     }
+    true
 }
 
 /// Generate all combinations of colors for a group
 fn generate_color_combinations(
     available_colors: &[u8],
     colors_needed: usize,
-    wilds_needed: usize,
+    wild_sets: &[Vec<Tile>],
     number: u8,
     melds: &mut Vec<Meld>,
 ) {
     if colors_needed == 0 {
         // Just wildcards
-        let mut tiles = VecDeque::new();
-        for _ in 0..wilds_needed {
-            tiles.push_back(Tile::wild());
-        }
-        if tiles.len() >= 3 {
-            melds.push(Meld::new(MeldType::Group, tiles));
+        for wilds in wild_sets {
+            if wilds.len() >= 3 && wilds_fit_group(&[], wilds) {
+                melds.push(Meld::new(MeldType::Group, wilds.iter().copied().collect()));
+            }
         }
         return;
     }
@@ -714,33 +1282,37 @@ fn generate_color_combinations(
         0,
         0,
         &mut combination,
-        wilds_needed,
+        wild_sets,
         number,
         melds,
     );
 }
 
 /// Helper for generating combinations
+#[allow(clippy::too_many_arguments)]
 fn generate_combinations_helper(
     available: &[u8],
     needed: usize,
     start: usize,
     index: usize,
     combination: &mut [u8],
-    wilds_needed: usize,
+    wild_sets: &[Vec<Tile>],
     number: u8,
     melds: &mut Vec<Meld>,
 ) {
     if index == needed {
-        // Build the group
-        let mut tiles = VecDeque::new();
-        for &color in &combination[..needed] {
-            tiles.push_back(Tile::new(color, number));
-        }
-        for _ in 0..wilds_needed {
-            tiles.push_back(Tile::wild());
+        // Build the group, once per joker choice that fits it
+        for wilds in wild_sets {
+            if !wilds_fit_group(&combination[..needed], wilds) {
+                continue;
+            }
+            let mut tiles = VecDeque::new();
+            for &color in &combination[..needed] {
+                tiles.push_back(Tile::new(color, number));
+            }
+            tiles.extend(wilds.iter().copied());
+            melds.push(Meld::new(MeldType::Group, tiles));
         }
-        melds.push(Meld::new(MeldType::Group, tiles));
         return;
     }
 
@@ -752,13 +1324,102 @@ fn generate_combinations_helper(
             i + 1,
             index + 1,
             combination,
-            wilds_needed,
+            wild_sets,
             number,
             melds,
         );
     }
 }
 
+/// Generate the melds the Twist jokers in the hand make possible.
+///
+/// Twist jokers only ever join real hand tiles, so each meld holds exactly one
+/// of them (see `TwistPower` for the shapes each power allows).
+fn generate_twist_melds(hand: &Hand, melds: &mut Vec<Meld>) {
+    let mut push_if_playable = |tiles: VecDeque<Tile>, meld_type: MeldType| {
+        let meld = Meld::new(meld_type, tiles);
+        if meld.is_valid() && can_play_meld(hand, &meld) {
+            melds.push(meld);
+        }
+    };
+
+    if hand.count(&Tile::twist(TwistPower::Duplicate)) > 0 {
+        let joker = Tile::twist(TwistPower::Duplicate);
+
+        // Runs of two or more real tiles with one number repeated
+        for color in 0..4 {
+            for start in 1..=12u8 {
+                for end in start + 1..=13u8 {
+                    if (start..=end).any(|n| hand.count(&Tile::new(color, n)) == 0) {
+                        break;
+                    }
+                    for repeated in start..=end {
+                        let mut tiles: VecDeque<Tile> = (start..=repeated).map(|n| Tile::new(color, n)).collect();
+                        tiles.push_back(joker);
+                        tiles.extend((repeated + 1..=end).map(|n| Tile::new(color, n)));
+                        push_if_playable(tiles, MeldType::Run);
+                    }
+                }
+            }
+        }
+
+        // Groups of two or more colors with one color doubled
+        for number in 1..=13 {
+            let colors: Vec<u8> = (0..4).filter(|&c| hand.count(&Tile::new(c, number)) > 0).collect();
+            for mask in 1u8..(1 << colors.len()) {
+                if mask.count_ones() < 2 {
+                    continue;
+                }
+                let mut tiles: VecDeque<Tile> = colors
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| mask & (1 << i) != 0)
+                    .map(|(_, &c)| Tile::new(c, number))
+                    .collect();
+                tiles.push_back(joker);
+                push_if_playable(tiles, MeldType::Group);
+            }
+        }
+    }
+
+    for power in [TwistPower::ColorChange, TwistPower::Mirror] {
+        let joker = Tile::twist(power);
+        if hand.count(&joker) == 0 {
+            continue;
+        }
+
+        // The joker stands for `middle`, with at least one real tile on each side
+        for left_color in 0..4 {
+            for middle in 2..=13u8 {
+                for left_start in (1..middle).rev() {
+                    if hand.count(&Tile::new(left_color, left_start)) == 0 {
+                        break;
+                    }
+                    let left: Vec<Tile> = (left_start..middle).map(|n| Tile::new(left_color, n)).collect();
+
+                    let right_sides: Vec<Vec<Tile>> = match power {
+                        TwistPower::ColorChange => (0..4)
+                            .filter(|&c| c != left_color)
+                            .flat_map(|c| (middle + 1..=13).map(move |end| (middle + 1..=end).map(|n| Tile::new(c, n)).collect()))
+                            .collect(),
+                        _ => (1..middle)
+                            .rev()
+                            .map(|end| (end..middle).rev().map(|n| Tile::new(left_color, n)).collect())
+                            .collect(),
+                    };
+
+                    for right in right_sides {
+                        let mut tiles: VecDeque<Tile> = left.iter().copied().collect();
+                        tiles.push_back(joker);
+                        tiles.extend(right);
+                        push_if_playable(tiles, MeldType::Run);
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Build a map from tiles to the indices of melds that use them
 fn build_tile_index(melds: &[Meld]) -> HashMap<Tile, Vec<usize>> {
     let mut index = HashMap::new();
@@ -785,6 +1446,7 @@ fn explore<F>(
     hand_to_beat: &Hand,
     timer: &TimeTracker,
     wild_debt: &WildDebt,
+    constraints: &PlayConstraints,
     best: &mut Option<(Vec<usize>, i32)>,
 ) where
     F: Fn(&Hand) -> i32,
@@ -803,8 +1465,12 @@ fn explore<F>(
             quality,
             hand_to_beat,
             wild_debt,
+            constraints,
             best,
         );
+        if best.is_some() {
+            timer.record_play();
+        }
         return;
     }
 
@@ -820,6 +1486,7 @@ fn explore<F>(
         hand_to_beat,
         timer,
         wild_debt,
+        constraints,
         best,
     );
 
@@ -851,6 +1518,7 @@ fn explore<F>(
             hand_to_beat,
             timer,
             wild_debt,
+            constraints,
             best,
         );
 
@@ -942,6 +1610,7 @@ fn evaluate_terminal_state<F>(
     quality: &F,
     hand_to_beat: &Hand,
     wild_debt: &WildDebt,
+    constraints: &PlayConstraints,
     best: &mut Option<(Vec<usize>, i32)>,
 ) where
     F: Fn(&Hand) -> i32,
@@ -951,6 +1620,15 @@ fn evaluate_terminal_state<F>(
         return;
     }
 
+    // Every must-play tile has to have left the hand
+    let must_play_met = constraints
+        .must_play
+        .iter()
+        .all(|(tile, &count)| remaining_hand.count(tile) as u16 + count as u16 <= hand_to_beat.count(tile) as u16);
+    if !must_play_met {
+        return;
+    }
+
     // Check if wild debt is satisfied by the played melds
     let played_melds: Vec<Meld> = active_melds
         .iter()
@@ -963,7 +1641,7 @@ fn evaluate_terminal_state<F>(
 
     // This is a valid solution - check if it's the best
     let score = quality(remaining_hand);
-    if best.as_ref().map_or(true, |(_, best_score)| score > *best_score) {
+    if best.as_ref().is_none_or(|(_, best_score)| score > *best_score) {
         *best = Some((active_melds.to_vec(), score));
     }
 }
@@ -1007,89 +1685,163 @@ fn beats(terminal: &Hand, baseline: &Hand) -> bool {
 
 /// Compute what tile a wild represents at a given position in a meld.
 ///
-/// For runs: the wild's position determines its number.
-/// For groups of 4: the wild represents the one missing color.
-/// For groups of 3: the wild could be either of two missing colors (EitherOf).
-fn compute_represented_tile(meld: &Meld, wild_position: usize) -> Option<RepresentedTile> {
-    match meld.meld_type {
-        MeldType::Run => {
-            // Find the color from any non-wild tile
-            let color = meld.tiles.iter().find_map(|t| t.color())?;
-
-            // Find the starting number by looking at non-wild tiles
-            // For each non-wild tile, we can compute: start = tile.number - position
-            let start = meld.tiles.iter().enumerate().find_map(|(i, t)| {
-                t.number().map(|n| n as i32 - i as i32)
-            })?;
-
-            // The wild at position `wild_position` represents start + wild_position
-            let represented_number = (start + wild_position as i32) as u8;
-            if represented_number >= 1 && represented_number <= 13 {
-                Some(RepresentedTile::Concrete(Tile::new(color, represented_number)))
-            } else {
-                None
-            }
-        }
-        MeldType::Group => {
-            // Find the number from any non-wild tile
-            let number = meld.tiles.iter().find_map(|t| t.number())?;
-
-            // Find which colors are present (non-wild tiles)
-            let colors_present: Vec<u8> = meld.tiles.iter()
-                .filter_map(|t| t.color())
-                .collect();
+/// For runs: the wild's position determines its number (runs keep their
+/// tiles in sequence order, see `Meld::new`). Numbers only wrap from 13 to 1
+/// when the rules allow wrapped runs.
+/// For groups: a wild represents one of the colors missing from the group.
+/// When every missing color must be filled by a wild (groups of 4) the answer
+/// is concrete; otherwise the wild could be any of the missing colors.
+fn compute_represented_tile(meld: &Meld, wild_position: usize, rules: &HouseRules) -> Option<RepresentedTile> {
+    let wild = meld.tiles.get(wild_position)?;
+    if !wild.is_wild() {
+        return None;
+    }
 
-            // Find missing colors (0-3)
-            let missing_colors: Vec<u8> = (0..4)
-                .filter(|c| !colors_present.contains(c))
-                .collect();
+    // Without the wrap-around rule a run's slots must stay within 1..=13
+    if meld.meld_type == MeldType::Run
+        && !rules.wrap_around_runs
+        && meld.twist_joker().is_none()
+        && !Meld::is_sequence_layout(&meld.tiles)
+    {
+        return None;
+    }
 
-            match missing_colors.len() {
-                1 => {
-                    // Group of 4 with one wild: wild represents the one missing color
-                    Some(RepresentedTile::Concrete(Tile::new(missing_colors[0], number)))
-                }
-                2 => {
-                    // Group of 3 with one wild: wild could be either missing color
-                    Some(RepresentedTile::EitherOf(
-                        Tile::new(missing_colors[0], number),
-                        Tile::new(missing_colors[1], number),
-                    ))
-                }
-                _ => {
-                    // More than 2 missing colors means multiple wilds -
-                    // each wild could be any missing color, but we need to be consistent
-                    // For simplicity, return the first missing color
-                    if !missing_colors.is_empty() {
-                        Some(RepresentedTile::Concrete(Tile::new(missing_colors[0], number)))
-                    } else {
-                        None
-                    }
-                }
-            }
+    // Runs, Twist jokers and color-restricted jokers stand for one definite
+    // tile: the one `Meld::wild_assignments` gives
+    if meld.meld_type == MeldType::Run || wild.twist_power().is_some() || wild.wild_color().is_some() {
+        return meld
+            .wild_assignments()
+            .into_iter()
+            .find(|a| a.position == wild_position)
+            .map(|a| RepresentedTile::Concrete(a.represents));
+    }
+
+    // An unrestricted wild in a group may stand for any missing color
+    let missing = missing_group_tiles(meld)?;
+    let wild_count = unrestricted_wild_count(meld);
+
+    if missing.len() <= wild_count {
+        // Every missing color is filled by a wild. With a single wild the
+        // answer is concrete; with several, each wild is still one of them
+        // but the debt as a whole covers all of them (see compute_wild_debts)
+        match missing.as_slice() {
+            [] => None,
+            [only] => Some(RepresentedTile::Concrete(*only)),
+            [a, b] => Some(RepresentedTile::EitherOf(*a, *b)),
+            _ => Some(RepresentedTile::OneOf(missing)),
+        }
+    } else {
+        match missing.as_slice() {
+            [a, b] => Some(RepresentedTile::EitherOf(*a, *b)),
+            _ => Some(RepresentedTile::OneOf(missing)),
         }
     }
 }
 
-/// Compute wild debts from a list of picked-up melds.
+/// The tiles of the group's number whose colors are left for unrestricted wilds:
+/// not present in the group and not claimed by a color-restricted joker.
 ///
-/// For each wild in each picked meld, we determine what tile it represents
-/// and add it to the debt structure.
-fn compute_wild_debts(picked_melds: &[(usize, Meld)]) -> WildDebt {
-    let mut debt = WildDebt::default();
+/// Returns None if the group has no numbered tile to take the number from.
+fn missing_group_tiles(meld: &Meld) -> Option<Vec<Tile>> {
+    // Find the number from any non-wild tile
+    let number = meld.tiles.iter().find_map(|t| t.number())?;
+
+    // Find which colors are present (non-wild tiles and restricted jokers)
+    let colors_present: Vec<u8> = meld
+        .tiles
+        .iter()
+        .filter_map(|t| t.color().or(t.wild_color()))
+        .collect();
 
-    for (_, meld) in picked_melds {
-        for (pos, tile) in meld.tiles.iter().enumerate() {
-            if tile.is_wild() {
-                if let Some(represented) = compute_represented_tile(meld, pos) {
-                    match represented {
-                        RepresentedTile::Concrete(t) => {
-                            *debt.concrete.entry(t).or_insert(0) += 1;
-                        }
-                        RepresentedTile::EitherOf(t1, t2) => {
-                            debt.either_or.push((t1, t2));
-                        }
+    Some(
+        (0..4)
+            .filter(|c| !colors_present.contains(c))
+            .map(|c| Tile::new(c, number))
+            .collect(),
+    )
+}
+
+/// Number of wilds in a meld that are not restricted to a color
+fn unrestricted_wild_count(meld: &Meld) -> usize {
+    meld.tiles.iter().filter(|t| t.is_wild() && t.wild_color().is_none()).count()
+}
+
+/// Compute wild debts from a list of picked-up melds.
+///
+/// Runs owe the exact tile each wild stands for. Groups owe one distinct
+/// missing color per wild: when the wilds fill every missing color those are
+/// concrete debts, a single wild between two colors is an either-or debt, and
+/// any other combination becomes a distinct-choice debt.
+///
+/// When the rules forbid retrieving wilds from groups of 3, such groups owe no
+/// replacement but must be laid down with all their tiles together instead.
+/// Melds holding a Twist joker are always kept together this way.
+fn compute_wild_debts(picked_melds: &[(usize, Meld)], rules: &HouseRules) -> WildDebt {
+    let mut debt = WildDebt {
+        rules: *rules,
+        ..WildDebt::default()
+    };
+
+    for (_, meld) in picked_melds {
+        for tile in &meld.tiles {
+            *debt.table_tiles.entry(*tile).or_insert(0) += 1;
+        }
+
+        let wild_count = meld.tiles.iter().filter(|t| t.is_wild()).count();
+        if wild_count == 0 {
+            continue;
+        }
+
+        // A Twist joker only works in the meld it shapes, so it never leaves it
+        let has_twist = meld.tiles.iter().any(|t| t.twist_power().is_some());
+        if has_twist
+            || (rules.no_retrieval_from_groups_of_three
+                && meld.meld_type == MeldType::Group
+                && meld.tiles.len() == 3)
+        {
+            debt.keep_intact.push(meld.clone());
+            continue;
+        }
+        debt.retrieved_wilds += wild_count as u8;
+
+        match meld.meld_type {
+            MeldType::Run => {
+                for pos in 0..meld.tiles.len() {
+                    if let Some(RepresentedTile::Concrete(t)) = compute_represented_tile(meld, pos, rules) {
+                        *debt.concrete.entry(t).or_insert(0) += 1;
+                    }
+                }
+            }
+            MeldType::Group => {
+                let Some(missing) = missing_group_tiles(meld) else {
+                    continue;
+                };
+
+                // Color-restricted jokers owe their own color
+                for pos in 0..meld.tiles.len() {
+                    if meld.tiles[pos].wild_color().is_some()
+                        && let Some(RepresentedTile::Concrete(t)) = compute_represented_tile(meld, pos, rules)
+                    {
+                        *debt.concrete.entry(t).or_insert(0) += 1;
+                    }
+                }
+
+                let wild_count = unrestricted_wild_count(meld);
+                if wild_count == 0 {
+                    continue;
+                }
+
+                if missing.len() <= wild_count {
+                    for t in missing {
+                        *debt.concrete.entry(t).or_insert(0) += 1;
                     }
+                } else if wild_count == 1 && missing.len() == 2 {
+                    debt.either_or.push((missing[0], missing[1]));
+                } else {
+                    debt.distinct.push(DistinctChoice {
+                        options: missing,
+                        count: wild_count as u8,
+                    });
                 }
             }
         }
@@ -1100,9 +1852,15 @@ fn compute_wild_debts(picked_melds: &[(usize, Meld)]) -> WildDebt {
 
 /// Check if the wild debt is satisfied by the tiles played in the given melds.
 ///
-/// Returns true if all debts are paid:
+/// Every debt must be paid by its own played tile - a single played tile never
+/// pays two debts:
 /// - For concrete debts: the tile must appear in played melds at least debt_count times
-/// - For either-or debts: at least one of the two options must appear in played melds
+/// - For either-or debts: one of the two options must be played
+/// - For distinct-choice debts: `count` different options must be played
+///
+/// The house rules in the debt add further checks: melds that must stay intact
+/// are contained in a single played meld, replacements may only count tiles
+/// from hand, and retrieved wilds must be replayed alongside a hand tile.
 fn is_wild_debt_satisfied(debt: &WildDebt, played_melds: &[Meld]) -> bool {
     // Count tiles played in all melds
     let mut played_counts: HashMap<Tile, u8> = HashMap::new();
@@ -1114,26 +1872,140 @@ fn is_wild_debt_satisfied(debt: &WildDebt, played_melds: &[Meld]) -> bool {
         }
     }
 
-    // Check concrete debts
-    for (tile, &required_count) in &debt.concrete {
-        let played = played_counts.get(tile).copied().unwrap_or(0);
-        if played < required_count {
+    if !intact_melds_laid_down(&debt.keep_intact, played_melds) {
+        return false;
+    }
+
+    // Played tiles that did not come from the table must have come from hand
+    let hand_counts: HashMap<Tile, u8> = played_counts
+        .iter()
+        .map(|(tile, &count)| {
+            let from_table = debt.table_tiles.get(tile).copied().unwrap_or(0);
+            (*tile, count.saturating_sub(from_table))
+        })
+        .collect();
+
+    if debt.rules.replay_with_hand_tile && debt.retrieved_wilds > 0 {
+        let replayed_wilds: usize = played_melds
+            .iter()
+            .filter(|m| {
+                m.tiles
+                    .iter()
+                    .any(|t| !t.is_wild() && hand_counts.get(t).copied().unwrap_or(0) > 0)
+            })
+            .map(|m| m.tiles.iter().filter(|t| t.is_wild()).count())
+            .sum();
+        if replayed_wilds < debt.retrieved_wilds as usize {
             return false;
         }
     }
 
-    // Check either-or debts
-    for (t1, t2) in &debt.either_or {
-        let played_t1 = played_counts.get(t1).copied().unwrap_or(0);
-        let played_t2 = played_counts.get(t2).copied().unwrap_or(0);
-        if played_t1 == 0 && played_t2 == 0 {
-            return false;
+    if debt.rules.replacement_from_hand_only {
+        pay_wild_debt(debt, hand_counts)
+    } else {
+        pay_wild_debt(debt, played_counts)
+    }
+}
+
+/// Check that the wild debt could be paid at all from the tiles of a hand.
+///
+/// Used to skip pickup combinations whose debts are out of reach before
+/// searching for melds.
+fn wild_debt_payable_from(debt: &WildDebt, hand: &Hand) -> bool {
+    let counts: HashMap<Tile, u8> = hand
+        .iter()
+        .filter(|(tile, _)| !tile.is_wild())
+        .map(|(tile, &count)| (*tile, count))
+        .collect();
+    pay_wild_debt(debt, counts)
+}
+
+/// Check that each meld in `intact` is contained, tiles and all, in its own played meld.
+fn intact_melds_laid_down(intact: &[Meld], played_melds: &[Meld]) -> bool {
+    let mut used = vec![false; played_melds.len()];
+
+    for meld in intact {
+        let found = played_melds.iter().enumerate().position(|(i, played)| {
+            !used[i]
+                && meld.tiles.iter().all(|tile| {
+                    let needed = meld.tiles.iter().filter(|t| *t == tile).count();
+                    played.tiles.iter().filter(|t| *t == tile).count() >= needed
+                })
+        });
+        match found {
+            Some(i) => used[i] = true,
+            None => return false,
         }
     }
 
     true
 }
 
+/// Pay the concrete and choice debts from the given tile counts.
+fn pay_wild_debt(debt: &WildDebt, mut counts: HashMap<Tile, u8>) -> bool {
+    // Check concrete debts, consuming the tiles that pay them
+    for (tile, &required_count) in &debt.concrete {
+        let available = counts.entry(*tile).or_insert(0);
+        if *available < required_count {
+            return false;
+        }
+        *available -= required_count;
+    }
+
+    // Either-or debts are distinct choices of one tile out of two
+    let mut choices: Vec<DistinctChoice> = debt
+        .either_or
+        .iter()
+        .map(|(t1, t2)| DistinctChoice {
+            options: vec![*t1, *t2],
+            count: 1,
+        })
+        .collect();
+    choices.extend(debt.distinct.iter().cloned());
+
+    pay_choice_debts(&choices, &mut counts)
+}
+
+/// Backtracking search for a way to pay every choice debt from the remaining
+/// played tiles, each debt taking `count` distinct options.
+fn pay_choice_debts(choices: &[DistinctChoice], remaining: &mut HashMap<Tile, u8>) -> bool {
+    let Some((first, rest)) = choices.split_first() else {
+        return true;
+    };
+    pay_distinct_options(first, 0, first.count, rest, remaining)
+}
+
+/// Pick `still_needed` options of `choice` starting at option index `from`,
+/// then continue with the remaining debts.
+fn pay_distinct_options(
+    choice: &DistinctChoice,
+    from: usize,
+    still_needed: u8,
+    rest: &[DistinctChoice],
+    remaining: &mut HashMap<Tile, u8>,
+) -> bool {
+    if still_needed == 0 {
+        return pay_choice_debts(rest, remaining);
+    }
+
+    for i in from..choice.options.len() {
+        let tile = choice.options[i];
+        if remaining.get(&tile).copied().unwrap_or(0) == 0 {
+            continue;
+        }
+
+        *remaining.get_mut(&tile).unwrap() -= 1;
+        let paid = pay_distinct_options(choice, i + 1, still_needed - 1, rest, remaining);
+        *remaining.get_mut(&tile).unwrap() += 1;
+
+        if paid {
+            return true;
+        }
+    }
+
+    false
+}
+
 // ============================================================================
 // Human Move Translation
 // ============================================================================
@@ -1141,13 +2013,37 @@ fn is_wild_debt_satisfied(debt: &WildDebt, played_melds: &[Meld]) -> bool {
 /// Translate a sequence of SolverMoves into human-readable HumanMoves.
 ///
 /// This function analyzes the before/after state of the table and hand to produce
-/// moves that describe transformations in terms humans can understand.
+/// moves that describe transformations in terms humans can understand. `rules`
+/// are the house rules the moves were found under.
 pub fn translate_to_human_moves(
     original_table: &Table,
     original_hand: &Hand,
     solver_moves: &[SolverMove],
+    rules: &HouseRules,
 ) -> Vec<HumanMove> {
-    // Extract picked up melds and laid down melds from solver moves
+    let (picked_melds, laid_down_melds) = split_solver_moves(original_table, solver_moves);
+
+    // If no moves, return empty
+    if laid_down_melds.is_empty() {
+        return Vec::new();
+    }
+
+    // Assign tile provenance
+    let assignments = assign_tile_provenance(&picked_melds, original_hand, &laid_down_melds);
+
+    // Build MeldOrigin for each new meld
+    let meld_origins = build_meld_origins(&laid_down_melds, &assignments);
+
+    // Build MeldFate for each picked-up meld
+    let meld_fates = build_meld_fates(&picked_melds, &assignments);
+
+    // Now analyze patterns and generate human moves
+    generate_human_moves(&picked_melds, &laid_down_melds, &meld_origins, &meld_fates, original_hand, rules)
+}
+
+/// Extract the picked-up melds (with their table indices) and the laid-down
+/// melds from solver moves
+fn split_solver_moves(original_table: &Table, solver_moves: &[SolverMove]) -> (Vec<(usize, Meld)>, Vec<Meld>) {
     let mut picked_melds: Vec<(usize, Meld)> = Vec::new();
     let mut laid_down_melds: Vec<Meld> = Vec::new();
 
@@ -1163,82 +2059,283 @@ pub fn translate_to_human_moves(
             }
         }
     }
+    (picked_melds, laid_down_melds)
+}
 
-    // If no moves, return empty
-    if laid_down_melds.is_empty() {
-        return Vec::new();
+/// Turn solver moves into ordered physical steps: splits first, then tiles
+/// moved between melds, then tiles placed from the rack.
+///
+/// Each laid-down meld is built on the picked-up meld that supplies most of
+/// its tiles (matched greedily, largest share first), so those tiles never
+/// move. A run whose leading or trailing tiles all go to a meld with no such
+/// base is split instead of being taken apart tile by tile. Tile provenance
+/// comes from the same minimum-cost assignment as `translate_to_human_moves`.
+pub fn plan_tile_actions(
+    original_table: &Table,
+    original_hand: &Hand,
+    solver_moves: &[SolverMove],
+) -> ManipulationPlan {
+    let (picked_melds, laid_down_melds) = split_solver_moves(original_table, solver_moves);
+    let assignments = assign_tile_provenance(&picked_melds, original_hand, &laid_down_melds);
+    let meld_origins = build_meld_origins(&laid_down_melds, &assignments);
+    let meld_fates = build_meld_fates(&picked_melds, &assignments);
+
+    // Match each laid-down meld to the picked-up meld supplying most of its tiles
+    let mut shares: Vec<(usize, usize, usize)> = Vec::new();
+    for fate in &meld_fates {
+        for new_idx in 0..laid_down_melds.len() {
+            let count = fate.tile_destinations.iter().filter(|d| **d == Some(new_idx)).count();
+            if count > 0 {
+                shares.push((count, fate.original_idx, new_idx));
+            }
+        }
+    }
+    shares.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+    let mut final_slots: Vec<Option<usize>> = vec![None; laid_down_melds.len()];
+    let mut based: HashSet<usize> = HashSet::new();
+    for (_, original_idx, new_idx) in shares {
+        if final_slots[new_idx].is_none() && based.insert(original_idx) {
+            final_slots[new_idx] = Some(original_idx);
+        }
     }
 
-    // Assign tile provenance
-    let assignments = assign_tile_provenance(&picked_melds, original_hand, &laid_down_melds);
+    let mut actions = Vec::new();
+    let mut next_slot = original_table.len();
+    // Current slot of every tile of the picked-up melds, by position
+    let mut tile_slots: Vec<Vec<usize>> =
+        meld_fates.iter().map(|fate| vec![fate.original_idx; fate.original.tiles.len()]).collect();
 
-    // Build MeldOrigin for each new meld
-    let meld_origins = build_meld_origins(&laid_down_melds, &assignments);
+    // Split off runs' trailing, then leading, blocks bound for unbased melds
+    for (fate, slots) in meld_fates.iter().zip(tile_slots.iter_mut()) {
+        if fate.original.meld_type != MeldType::Run {
+            continue;
+        }
+        let len = fate.tile_destinations.len();
+        let suffix = split_block(fate.tile_destinations.iter().rev(), &final_slots);
+        if let Some((new_idx, block)) = suffix.filter(|(_, block)| *block < len) {
+            actions.push(TileAction::Split { slot: fate.original_idx, after: len - block, new_slot: next_slot });
+            final_slots[new_idx] = Some(next_slot);
+            slots[len - block..].fill(next_slot);
+            next_slot += 1;
+        }
+        let remaining = slots.iter().filter(|&&slot| slot == fate.original_idx).count();
+        let prefix = split_block(fate.tile_destinations[..remaining].iter(), &final_slots);
+        if let Some((new_idx, block)) = prefix.filter(|(_, block)| *block < remaining) {
+            // The leading block keeps the slot; the rest moves to the new one
+            actions.push(TileAction::Split { slot: fate.original_idx, after: block, new_slot: next_slot });
+            for slot in &mut slots[block..remaining] {
+                *slot = next_slot;
+            }
+            for target in final_slots.iter_mut() {
+                if *target == Some(fate.original_idx) {
+                    *target = Some(next_slot);
+                }
+            }
+            final_slots[new_idx] = Some(fate.original_idx);
+            next_slot += 1;
+        }
+    }
 
-    // Build MeldFate for each picked-up meld
-    let meld_fates = build_meld_fates(&picked_melds, &assignments);
+    // Melds with no base are started in fresh slots
+    let final_slots: Vec<usize> = final_slots
+        .into_iter()
+        .map(|slot| {
+            slot.unwrap_or_else(|| {
+                next_slot += 1;
+                next_slot - 1
+            })
+        })
+        .collect();
 
-    // Now analyze patterns and generate human moves
-    generate_human_moves(&picked_melds, &laid_down_melds, &meld_origins, &meld_fates, original_hand)
+    // Move table tiles that are not yet where they belong
+    for (fate, slots) in meld_fates.iter().zip(&tile_slots) {
+        for ((tile, dest), &from) in fate.original.tiles.iter().zip(&fate.tile_destinations).zip(slots) {
+            match dest {
+                Some(new_idx) if final_slots[*new_idx] != from => {
+                    actions.push(TileAction::MoveTile { tile: *tile, from, to: final_slots[*new_idx] });
+                }
+                Some(_) => {}
+                None => actions.push(TileAction::TakeToRack { tile: *tile, from }),
+            }
+        }
+    }
+
+    // Place the rack tiles
+    for origin in &meld_origins {
+        for (tile, source) in origin.new_meld.tiles.iter().zip(&origin.tile_sources) {
+            if *source == TileSource::Hand {
+                actions.push(TileAction::PlaceFromRack { tile: *tile, to: final_slots[origin.new_idx] });
+            }
+        }
+    }
+
+    ManipulationPlan { actions, final_slots }
 }
 
-/// Assign tile provenance - determine which source tile maps to which destination tile
+/// The meld the first tiles of `destinations` all go to, and how many tiles
+/// that block holds, if that meld has no base slot yet
+fn split_block<'a>(
+    mut destinations: impl Iterator<Item = &'a Option<usize>>,
+    final_slots: &[Option<usize>],
+) -> Option<(usize, usize)> {
+    let new_idx = (*destinations.next()?)?;
+    if final_slots[new_idx].is_some() {
+        return None;
+    }
+    let block = 1 + destinations.take_while(|d| **d == Some(new_idx)).count();
+    Some((new_idx, block))
+}
+
+/// Cost of taking a tile from the hand when a picked-up meld could supply it;
+/// above any table cost, so hand tiles are used only when needed
+const HAND_SOURCE_COST: u32 = 100;
+/// Cost of a table tile that ends up in a new meld sharing no tiles with its
+/// original meld; each shared tile makes the move one cheaper
+const TABLE_SOURCE_COST: u32 = 14;
+/// Largest number of sources of one tile kind assigned exactly; beyond it
+/// each destination greedily takes its cheapest source
+const MAX_EXACT_SOURCES: usize = 12;
+
+/// Assign tile provenance - determine which source tile maps to which destination tile.
+///
+/// Sources and destinations of each tile kind are matched at minimum cost:
+/// table tiles before hand tiles, and a table tile preferably into the new
+/// meld sharing the most tiles with its original meld. Two R5s from different
+/// melds thus each stay with the meld they came from.
 fn assign_tile_provenance(
     picked_melds: &[(usize, Meld)],
     hand: &Hand,
     new_melds: &[Meld],
 ) -> Vec<TileAssignment> {
-    // Build source pool: list of (Tile, TileSource)
-    let mut source_pool: Vec<(Tile, TileSource)> = Vec::new();
+    // Sources and destinations (new meld index, position) per tile kind
+    let mut sources: HashMap<Tile, Vec<TileSource>> = HashMap::new();
+    let mut destinations: HashMap<Tile, Vec<(usize, usize)>> = HashMap::new();
 
     for (idx, meld) in picked_melds {
         for tile in &meld.tiles {
-            source_pool.push((*tile, TileSource::TableMeld(*idx)));
+            sources.entry(*tile).or_default().push(TileSource::TableMeld(*idx));
         }
     }
     for (tile, &count) in hand.iter() {
         for _ in 0..count {
-            source_pool.push((*tile, TileSource::Hand));
+            sources.entry(*tile).or_default().push(TileSource::Hand);
+        }
+    }
+    for (meld_idx, meld) in new_melds.iter().enumerate() {
+        for (pos, tile) in meld.tiles.iter().enumerate() {
+            destinations.entry(*tile).or_default().push((meld_idx, pos));
         }
     }
 
-    // Greedy assignment: prefer table sources over hand sources
-    let mut assignments = Vec::new();
-    let mut used = vec![false; source_pool.len()];
+    let cost = |source: &TileSource, dest_meld_idx: usize| match source {
+        TileSource::Hand => HAND_SOURCE_COST,
+        TileSource::TableMeld(idx) => {
+            let original = picked_melds.iter().find(|(i, _)| i == idx).map(|(_, m)| m);
+            let shared = original.map_or(0, |m| shared_tile_count(m, &new_melds[dest_meld_idx]));
+            TABLE_SOURCE_COST.saturating_sub(shared as u32)
+        }
+    };
 
-    for (meld_idx, meld) in new_melds.iter().enumerate() {
-        for tile in meld.tiles.iter() {
-            // First try to find matching table source
-            let source_idx = source_pool
-                .iter()
-                .enumerate()
-                .position(|(i, (t, src))| {
-                    !used[i] && *t == *tile && matches!(src, TileSource::TableMeld(_))
-                })
-                .or_else(|| {
-                    // Fall back to hand source
-                    source_pool
-                        .iter()
-                        .enumerate()
-                        .position(|(i, (t, _))| !used[i] && *t == *tile)
-                });
+    let mut placed: Vec<((usize, usize), TileAssignment)> = Vec::new();
+    for (tile, dests) in &destinations {
+        let Some(tile_sources) = sources.get(tile) else {
+            continue;
+        };
+        let costs: Vec<Vec<u32>> = dests
+            .iter()
+            .map(|(meld_idx, _)| tile_sources.iter().map(|src| cost(src, *meld_idx)).collect())
+            .collect();
 
-            if let Some(i) = source_idx {
-                used[i] = true;
-                assignments.push(TileAssignment {
+        for (dest, source) in dests.iter().zip(min_cost_assignment(&costs)) {
+            if let Some(source) = source {
+                placed.push((*dest, TileAssignment {
                     tile: *tile,
-                    source: source_pool[i].1,
-                    dest_meld_idx: meld_idx,
-                });
+                    source: tile_sources[source],
+                    dest_meld_idx: dest.0,
+                }));
+            }
+        }
+    }
+
+    // Keep the assignments in new meld order
+    placed.sort_by_key(|(dest, _)| *dest);
+    placed.into_iter().map(|(_, assignment)| assignment).collect()
+}
+
+/// Number of tiles two melds have in common, counting copies
+fn shared_tile_count(a: &Meld, b: &Meld) -> usize {
+    let mut rest: Vec<Tile> = b.tiles.iter().copied().collect();
+    a.tiles
+        .iter()
+        .filter(|tile| match rest.iter().position(|t| t == *tile) {
+            Some(pos) => {
+                rest.swap_remove(pos);
+                true
             }
+            None => false,
+        })
+        .count()
+}
+
+/// Give each destination its own source at the lowest total cost, where
+/// `costs[d][s]` is the cost of source `s` for destination `d`. Destinations
+/// left without a source come back as None.
+///
+/// Solved exactly by dynamic programming over the sets of used sources, or
+/// greedily when there are more than `MAX_EXACT_SOURCES` sources.
+fn min_cost_assignment(costs: &[Vec<u32>]) -> Vec<Option<usize>> {
+    let source_count = costs.first().map_or(0, |row| row.len());
+    // Leaving a destination without a source costs more than any source
+    let unassigned = HAND_SOURCE_COST + 1;
+
+    if source_count > MAX_EXACT_SOURCES {
+        let mut used = vec![false; source_count];
+        return costs
+            .iter()
+            .map(|row| {
+                let best = (0..source_count).filter(|&s| !used[s]).min_by_key(|&s| row[s]);
+                if let Some(s) = best {
+                    used[s] = true;
+                }
+                best
+            })
+            .collect();
+    }
+
+    // best[d][mask]: cheapest cost for destinations d.. when the sources in
+    // mask are already taken
+    let masks = 1usize << source_count;
+    let mut best = vec![vec![0u32; masks]; costs.len() + 1];
+    for d in (0..costs.len()).rev() {
+        for mask in 0..masks {
+            let mut cheapest = unassigned + best[d + 1][mask];
+            for s in (0..source_count).filter(|s| mask & (1 << s) == 0) {
+                cheapest = cheapest.min(costs[d][s] + best[d + 1][mask | (1 << s)]);
+            }
+            best[d][mask] = cheapest;
         }
     }
 
-    assignments
+    // Walk the table forward to recover the choices
+    let mut mask = 0;
+    let mut choices = Vec::with_capacity(costs.len());
+    for d in 0..costs.len() {
+        let choice = (0..source_count)
+            .filter(|s| mask & (1 << s) == 0)
+            .find(|&s| costs[d][s] + best[d + 1][mask | (1 << s)] == best[d][mask]);
+        if let Some(s) = choice {
+            mask |= 1 << s;
+        }
+        choices.push(choice);
+    }
+    choices
 }
 
 /// Build MeldOrigin for each new meld
 fn build_meld_origins(new_melds: &[Meld], assignments: &[TileAssignment]) -> Vec<MeldOrigin> {
+    // Each assignment accounts for one tile, so copies of a tile in the same
+    // meld get their own sources
+    let mut used = vec![false; assignments.len()];
     new_melds
         .iter()
         .enumerate()
@@ -1247,11 +2344,15 @@ fn build_meld_origins(new_melds: &[Meld], assignments: &[TileAssignment]) -> Vec
                 .tiles
                 .iter()
                 .map(|tile| {
-                    // Find the assignment for this tile in this meld
+                    // Find an unused assignment for this tile in this meld
                     assignments
                         .iter()
-                        .find(|a| a.dest_meld_idx == idx && a.tile == *tile)
-                        .map(|a| a.source)
+                        .enumerate()
+                        .find(|(i, a)| !used[*i] && a.dest_meld_idx == idx && a.tile == *tile)
+                        .map(|(i, a)| {
+                            used[i] = true;
+                            a.source
+                        })
                         .unwrap_or(TileSource::Hand)
                 })
                 .collect();
@@ -1270,6 +2371,7 @@ fn build_meld_fates(
     picked_melds: &[(usize, Meld)],
     assignments: &[TileAssignment],
 ) -> Vec<MeldFate> {
+    let mut used = vec![false; assignments.len()];
     picked_melds
         .iter()
         .map(|(orig_idx, meld)| {
@@ -1277,13 +2379,19 @@ fn build_meld_fates(
                 .tiles
                 .iter()
                 .map(|tile| {
-                    // Find where this tile ended up
+                    // Find where this copy of the tile ended up
                     assignments
                         .iter()
-                        .find(|a| {
-                            a.tile == *tile && matches!(a.source, TileSource::TableMeld(i) if i == *orig_idx)
+                        .enumerate()
+                        .find(|(i, a)| {
+                            !used[*i]
+                                && a.tile == *tile
+                                && matches!(a.source, TileSource::TableMeld(m) if m == *orig_idx)
+                        })
+                        .map(|(i, a)| {
+                            used[i] = true;
+                            a.dest_meld_idx
                         })
-                        .map(|a| a.dest_meld_idx)
                 })
                 .collect();
 
@@ -1296,6 +2404,30 @@ fn build_meld_fates(
         .collect()
 }
 
+/// The span `lo..=hi` of positions in the original run whose tiles went to
+/// the new run `dest_idx`, when its hand tiles only extend it past an end of
+/// the original run. None when a hand tile lands between two tiles that were
+/// adjacent in the original run.
+fn extended_span(fate: &MeldFate, dest_idx: usize, origin: &MeldOrigin) -> Option<(usize, usize)> {
+    let positions: Vec<usize> = (0..fate.tile_destinations.len())
+        .filter(|&i| fate.tile_destinations[i] == Some(dest_idx))
+        .collect();
+    let (&lo, &hi) = (positions.first()?, positions.last()?);
+
+    let from_table: Vec<usize> = (0..origin.tile_sources.len())
+        .filter(|&i| !matches!(origin.tile_sources[i], TileSource::Hand))
+        .collect();
+    let (&first, &last) = (from_table.first()?, from_table.last()?);
+    let gap_filled = from_table.len() != last - first + 1;
+    let inserted_before = first > 0 && lo > 0;
+    let inserted_after = last + 1 < origin.tile_sources.len() && hi + 1 < fate.original.tiles.len();
+    if gap_filled || inserted_before || inserted_after {
+        None
+    } else {
+        Some((lo, hi))
+    }
+}
+
 /// Generate human-readable moves from the analyzed data
 fn generate_human_moves(
     picked_melds: &[(usize, Meld)],
@@ -1303,6 +2435,7 @@ fn generate_human_moves(
     meld_origins: &[MeldOrigin],
     meld_fates: &[MeldFate],
     _original_hand: &Hand,
+    rules: &HouseRules,
 ) -> Vec<HumanMove> {
     let mut human_moves = Vec::new();
     let mut processed_new_melds = HashSet::new();
@@ -1352,7 +2485,14 @@ fn generate_human_moves(
                 })
                 .collect();
 
-            if !hand_tiles.is_empty() && origin.new_meld.tiles.len() > fate.original.tiles.len() {
+            // Tiles taken from other melds (such as a swapped-out wild) make it
+            // more than an extension
+            let only_own_tiles = origin
+                .tile_sources
+                .iter()
+                .all(|s| matches!(s, TileSource::Hand) || matches!(s, TileSource::TableMeld(idx) if *idx == fate.original_idx));
+
+            if !hand_tiles.is_empty() && only_own_tiles && origin.new_meld.tiles.len() > fate.original.tiles.len() {
                 // This is an ExtendMeld
                 human_moves.push(HumanMove::ExtendMeld {
                     original: fate.original.clone(),
@@ -1381,9 +2521,11 @@ fn generate_human_moves(
             .filter_map(|d| *d)
             .collect();
 
-        // If tiles went to multiple destinations and all destinations only have tiles from this source
+        // If tiles went to multiple destinations and all destinations only have
+        // tiles from this source (plus, for runs split into runs, hand tiles)
         if destinations.len() >= 2 {
             let mut is_pure_split = true;
+            let mut inserted = Vec::new();
             for &dest_idx in &destinations {
                 if processed_new_melds.contains(&dest_idx) {
                     is_pure_split = false;
@@ -1391,29 +2533,76 @@ fn generate_human_moves(
                 }
                 let origin = &meld_origins[dest_idx];
                 // Check if this new meld only has tiles from this original meld
-                for src in &origin.tile_sources {
-                    if let TileSource::TableMeld(idx) = src {
-                        if *idx != fate.original_idx {
+                for (tile, src) in origin.new_meld.tiles.iter().zip(origin.tile_sources.iter()) {
+                    match src {
+                        TileSource::TableMeld(idx) if *idx == fate.original_idx => {}
+                        TileSource::Hand => inserted.push(*tile),
+                        TileSource::TableMeld(_) => {
                             is_pure_split = false;
                             break;
                         }
-                    } else {
-                        is_pure_split = false;
-                        break;
                     }
                 }
             }
 
-            if is_pure_split {
-                let parts: Vec<Meld> = destinations
-                    .iter()
-                    .map(|&idx| meld_origins[idx].new_meld.clone())
-                    .collect();
+            let parts: Vec<Meld> = destinations
+                .iter()
+                .map(|&idx| meld_origins[idx].new_meld.clone())
+                .collect();
+            let runs_into_runs = fate.original.meld_type == MeldType::Run
+                && parts.iter().all(|p| p.meld_type == MeldType::Run);
 
-                human_moves.push(HumanMove::SplitMeld {
-                    original: fate.original.clone(),
-                    parts,
-                });
+            if is_pure_split && (inserted.is_empty() || runs_into_runs) {
+                // Hand tiles that only extend the ends of the pieces make a
+                // split followed by extensions, not an insert
+                let end_spans: Option<Vec<(usize, usize)>> = if inserted.is_empty() {
+                    None
+                } else {
+                    destinations
+                        .iter()
+                        .map(|&dest_idx| extended_span(fate, dest_idx, &meld_origins[dest_idx]))
+                        .collect()
+                };
+
+                if inserted.is_empty() {
+                    human_moves.push(HumanMove::SplitMeld {
+                        original: fate.original.clone(),
+                        parts,
+                    });
+                } else if let Some(spans) = end_spans {
+                    let pieces: Vec<Meld> = spans
+                        .iter()
+                        .map(|&(lo, hi)| Meld::new(MeldType::Run, fate.original.tiles.range(lo..=hi).copied().collect()))
+                        .collect();
+                    human_moves.push(HumanMove::SplitMeld {
+                        original: fate.original.clone(),
+                        parts: pieces.clone(),
+                    });
+                    for (piece, &dest_idx) in pieces.into_iter().zip(destinations.iter()) {
+                        let origin = &meld_origins[dest_idx];
+                        let added_tiles: Vec<Tile> = origin
+                            .new_meld
+                            .tiles
+                            .iter()
+                            .zip(origin.tile_sources.iter())
+                            .filter(|(_, src)| matches!(src, TileSource::Hand))
+                            .map(|(tile, _)| *tile)
+                            .collect();
+                        if !added_tiles.is_empty() {
+                            human_moves.push(HumanMove::ExtendMeld {
+                                original: piece,
+                                added_tiles,
+                                result: origin.new_meld.clone(),
+                            });
+                        }
+                    }
+                } else {
+                    human_moves.push(HumanMove::InsertAndSplit {
+                        original: fate.original.clone(),
+                        inserted,
+                        parts,
+                    });
+                }
 
                 for dest_idx in destinations {
                     processed_new_melds.insert(dest_idx);
@@ -1423,35 +2612,137 @@ fn generate_human_moves(
         }
     }
 
-    // Fourth pass: detect JoinMelds (multiple originals become one new meld)
-    for origin in meld_origins {
-        if processed_new_melds.contains(&origin.new_idx) {
+    // Fourth pass: detect SwapWild. Each wild taken from a meld must be
+    // replaced by a hand tile it represented; the wild itself may stay loose
+    // or go on to a new meld.
+    for fate in meld_fates {
+        if processed_old_melds.contains(&fate.original_idx) {
             continue;
         }
 
-        // Find all unique table sources for this meld
-        let table_sources: HashSet<usize> = origin
-            .tile_sources
+        let wild_positions: Vec<usize> = fate
+            .original
+            .tiles
             .iter()
-            .filter_map(|s| {
-                if let TileSource::TableMeld(idx) = s {
-                    Some(*idx)
-                } else {
-                    None
-                }
-            })
+            .enumerate()
+            .filter(|(_, t)| t.is_wild())
+            .map(|(i, _)| i)
             .collect();
 
-        // If multiple table sources and no hand tiles, this is a JoinMelds
-        if table_sources.len() >= 2 {
-            let has_hand_tiles = origin.tile_sources.iter().any(|s| matches!(s, TileSource::Hand));
+        if wild_positions.is_empty() {
+            continue;
+        }
 
-            if !has_hand_tiles {
-                // Check all sources are unprocessed
-                if table_sources.iter().all(|idx| !processed_old_melds.contains(idx)) {
-                    let sources: Vec<Meld> = table_sources
-                        .iter()
-                        .filter_map(|idx| {
+        // The real tiles must all stay together in one meld
+        let destinations: HashSet<usize> = fate
+            .original
+            .tiles
+            .iter()
+            .zip(fate.tile_destinations.iter())
+            .filter(|(t, _)| !t.is_wild())
+            .filter_map(|(_, d)| *d)
+            .collect();
+        let real_count = fate.original.tiles.len() - wild_positions.len();
+        let placed_real = fate
+            .original
+            .tiles
+            .iter()
+            .zip(fate.tile_destinations.iter())
+            .filter(|(t, d)| !t.is_wild() && d.is_some())
+            .count();
+        if destinations.len() != 1 || placed_real != real_count {
+            continue;
+        }
+        let dest_idx = *destinations.iter().next().unwrap();
+        if processed_new_melds.contains(&dest_idx) {
+            continue;
+        }
+
+        // The new meld holds exactly those tiles plus one hand tile per wild
+        let origin = &meld_origins[dest_idx];
+        if origin.new_meld.tiles.len() != fate.original.tiles.len() {
+            continue;
+        }
+        let from_original = origin
+            .tile_sources
+            .iter()
+            .filter(|s| matches!(s, TileSource::TableMeld(idx) if *idx == fate.original_idx))
+            .count();
+        let hand_tiles: Vec<Tile> = origin
+            .new_meld
+            .tiles
+            .iter()
+            .zip(origin.tile_sources.iter())
+            .filter(|(_, s)| matches!(s, TileSource::Hand))
+            .map(|(t, _)| *t)
+            .collect();
+        if from_original != real_count || hand_tiles.len() != wild_positions.len() {
+            continue;
+        }
+
+        // Match each wild to an unused hand tile it stood for
+        let mut used = vec![false; hand_tiles.len()];
+        let mut swaps = Vec::new();
+        for &pos in &wild_positions {
+            let options = match compute_represented_tile(&fate.original, pos, rules) {
+                Some(RepresentedTile::Concrete(tile)) => vec![tile],
+                Some(RepresentedTile::EitherOf(a, b)) => vec![a, b],
+                Some(RepresentedTile::OneOf(tiles)) => tiles,
+                None => break,
+            };
+            let Some(i) = (0..hand_tiles.len()).find(|&i| !used[i] && options.contains(&hand_tiles[i])) else {
+                break;
+            };
+            used[i] = true;
+            swaps.push((hand_tiles[i], fate.original.tiles[pos]));
+        }
+
+        if swaps.len() == wild_positions.len() {
+            human_moves.push(HumanMove::SwapWild {
+                original: fate.original.clone(),
+                swaps,
+                result: origin.new_meld.clone(),
+            });
+            processed_new_melds.insert(dest_idx);
+            processed_old_melds.insert(fate.original_idx);
+        }
+    }
+
+    // Fifth pass: detect JoinMelds (multiple originals become one new meld)
+    for origin in meld_origins {
+        if processed_new_melds.contains(&origin.new_idx) {
+            continue;
+        }
+
+        // Find all unique table sources for this meld
+        let table_sources: HashSet<usize> = origin
+            .tile_sources
+            .iter()
+            .filter_map(|s| {
+                if let TileSource::TableMeld(idx) = s {
+                    Some(*idx)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        // If multiple table sources and no hand tiles, this is a JoinMelds
+        if table_sources.len() >= 2 {
+            let has_hand_tiles = origin.tile_sources.iter().any(|s| matches!(s, TileSource::Hand));
+
+            if !has_hand_tiles {
+                // Check all sources are unprocessed and go into this meld whole
+                let whole_sources = table_sources.iter().all(|idx| {
+                    meld_fates
+                        .iter()
+                        .find(|f| f.original_idx == *idx)
+                        .is_some_and(|f| f.tile_destinations.iter().all(|d| *d == Some(origin.new_idx)))
+                });
+                if whole_sources && table_sources.iter().all(|idx| !processed_old_melds.contains(idx)) {
+                    let sources: Vec<Meld> = table_sources
+                        .iter()
+                        .filter_map(|idx| {
                             picked_melds.iter().find(|(i, _)| i == idx).map(|(_, m)| m.clone())
                         })
                         .collect();
@@ -1470,71 +2761,86 @@ fn generate_human_moves(
         }
     }
 
-    // Fifth pass: detect SwapWild
+    // Sixth pass: detect MoveTile (a meld gives up one tile, which joins
+    // another whole meld or starts a new one, plus any hand tiles)
     for fate in meld_fates {
-        if processed_old_melds.contains(&fate.original_idx) {
+        if processed_old_melds.contains(&fate.original_idx) || fate.tile_destinations.iter().any(|d| d.is_none()) {
             continue;
         }
 
-        // Check if original has wilds and a new meld has the same tiles but with wilds replaced
-        let wild_positions: Vec<usize> = fate
-            .original
-            .tiles
-            .iter()
-            .enumerate()
-            .filter(|(_, t)| t.is_wild())
-            .map(|(i, _)| i)
-            .collect();
+        let mut counts: HashMap<usize, usize> = HashMap::new();
+        for dest in fate.tile_destinations.iter().flatten() {
+            *counts.entry(*dest).or_default() += 1;
+        }
+        if counts.len() != 2 {
+            continue;
+        }
+        let Some(&to_idx) = counts.iter().find(|(_, c)| **c == 1).map(|(d, _)| d) else {
+            continue;
+        };
+        let Some(&rest_idx) = counts.keys().find(|d| **d != to_idx) else {
+            continue;
+        };
+        if processed_new_melds.contains(&to_idx) || processed_new_melds.contains(&rest_idx) {
+            continue;
+        }
 
-        if wild_positions.is_empty() {
+        // What is left must be exactly the other tiles of this meld
+        let rest = &meld_origins[rest_idx];
+        if rest.new_meld.tiles.len() + 1 != fate.original.tiles.len() {
             continue;
         }
 
-        // Find the destination meld
-        let destinations: HashSet<usize> = fate
-            .tile_destinations
+        // Besides the moved tile, the destination holds hand tiles and at
+        // most one other meld, all of whose tiles end up there
+        let dest = &meld_origins[to_idx];
+        let others: HashSet<usize> = dest
+            .tile_sources
             .iter()
-            .filter_map(|d| *d)
+            .filter_map(|s| match s {
+                TileSource::TableMeld(idx) if *idx != fate.original_idx => Some(*idx),
+                _ => None,
+            })
             .collect();
-
-        if destinations.len() == 1 {
-            let dest_idx = *destinations.iter().next().unwrap();
-            if processed_new_melds.contains(&dest_idx) {
-                continue;
-            }
-
-            let origin = &meld_origins[dest_idx];
-
-            // Check if the new meld has the same structure but with wilds replaced
-            if origin.new_meld.tiles.len() == fate.original.tiles.len() {
-                let mut swaps = Vec::new();
-                let mut is_swap = true;
-
-                for pos in &wild_positions {
-                    // Check if the tile at this position is now from hand
-                    if *pos < origin.tile_sources.len() {
-                        if matches!(origin.tile_sources[*pos], TileSource::Hand) {
-                            let replacement = origin.new_meld.tiles[*pos];
-                            let wild = Tile::wild();
-                            swaps.push((replacement, wild));
-                        } else {
-                            is_swap = false;
-                            break;
-                        }
-                    }
+        if others.len() > 1 {
+            continue;
+        }
+        let to = match others.iter().next() {
+            Some(idx) => match meld_fates.iter().find(|f| f.original_idx == *idx) {
+                Some(other)
+                    if !processed_old_melds.contains(idx)
+                        && other.tile_destinations.iter().all(|d| *d == Some(to_idx)) =>
+                {
+                    Some(other)
                 }
+                _ => continue,
+            },
+            None => None,
+        };
+        let added_tiles: Vec<Tile> = dest
+            .new_meld
+            .tiles
+            .iter()
+            .zip(dest.tile_sources.iter())
+            .filter(|(_, s)| matches!(s, TileSource::Hand))
+            .map(|(t, _)| *t)
+            .collect();
 
-                if is_swap && !swaps.is_empty() {
-                    human_moves.push(HumanMove::SwapWild {
-                        original: fate.original.clone(),
-                        swaps,
-                        result: origin.new_meld.clone(),
-                    });
-                    processed_new_melds.insert(dest_idx);
-                    processed_old_melds.insert(fate.original_idx);
-                }
-            }
+        let pos = fate.tile_destinations.iter().position(|d| *d == Some(to_idx)).unwrap();
+        human_moves.push(HumanMove::MoveTile {
+            tile: fate.original.tiles[pos],
+            from: fate.original.clone(),
+            remaining: rest.new_meld.clone(),
+            to: to.map(|other| other.original.clone()),
+            added_tiles,
+            result: dest.new_meld.clone(),
+        });
+        processed_old_melds.insert(fate.original_idx);
+        if let Some(other) = to {
+            processed_old_melds.insert(other.original_idx);
         }
+        processed_new_melds.insert(rest_idx);
+        processed_new_melds.insert(to_idx);
     }
 
     // Final pass: anything remaining becomes a Rearrange
@@ -1588,6 +2894,218 @@ fn meld_tiles_equal(a: &Meld, b: &Meld) -> bool {
     a.tiles.iter().zip(b.tiles.iter()).all(|(t1, t2)| t1 == t2)
 }
 
+/// Carry out human moves on copies of a table and hand, checking each move
+/// against the table as it stands.
+///
+/// Tiles a move frees without placing (wilds swapped out, leftovers of a
+/// rearrangement) stay loose for later rearrangements to use, and return to
+/// the hand at the end. Fails on the first move that does not fit.
+pub fn apply_human_moves(table: &Table, hand: &Hand, moves: &[HumanMove]) -> Result<(Table, Hand), String> {
+    let mut melds: Vec<Meld> = table.melds().to_vec();
+    let mut hand = hand.clone();
+    let mut loose: Vec<Tile> = Vec::new();
+
+    for (step, human_move) in moves.iter().enumerate() {
+        let fail = |reason: &str| format!("Move {}: {}", step + 1, reason);
+        match human_move {
+            HumanMove::PlayFromHand(meld) => {
+                take_from_hand(&mut hand, meld.tiles.iter()).map_err(|e| fail(&e))?;
+                melds.push(meld.clone());
+            }
+            HumanMove::ExtendMeld { original, added_tiles, result } => {
+                take_meld(&mut melds, original).map_err(|e| fail(&e))?;
+                take_from_hand(&mut hand, added_tiles.iter()).map_err(|e| fail(&e))?;
+                check_same_tiles(original.tiles.iter().chain(added_tiles), result.tiles.iter()).map_err(|e| fail(&e))?;
+                melds.push(result.clone());
+            }
+            HumanMove::TakeFromMeld { original, taken_tiles, remaining } => {
+                take_meld(&mut melds, original).map_err(|e| fail(&e))?;
+                check_same_tiles(original.tiles.iter(), remaining.tiles.iter().chain(taken_tiles)).map_err(|e| fail(&e))?;
+                loose.extend(taken_tiles.iter().copied());
+                melds.push(remaining.clone());
+            }
+            HumanMove::SplitMeld { original, parts } => {
+                take_meld(&mut melds, original).map_err(|e| fail(&e))?;
+                check_same_tiles(original.tiles.iter(), parts.iter().flat_map(|p| p.tiles.iter())).map_err(|e| fail(&e))?;
+                melds.extend(parts.iter().cloned());
+            }
+            HumanMove::JoinMelds { sources, result } => {
+                for source in sources {
+                    take_meld(&mut melds, source).map_err(|e| fail(&e))?;
+                }
+                check_same_tiles(sources.iter().flat_map(|m| m.tiles.iter()), result.tiles.iter()).map_err(|e| fail(&e))?;
+                melds.push(result.clone());
+            }
+            HumanMove::SwapWild { original, swaps, result } => {
+                take_meld(&mut melds, original).map_err(|e| fail(&e))?;
+                take_from_hand(&mut hand, swaps.iter().map(|(replacement, _)| replacement)).map_err(|e| fail(&e))?;
+                let mut expected: Vec<Tile> = original.tiles.iter().copied().collect();
+                for (replacement, wild) in swaps {
+                    let pos = expected.iter().position(|t| t == wild).ok_or_else(|| fail("swapped wild not in meld"))?;
+                    expected[pos] = *replacement;
+                    loose.push(*wild);
+                }
+                check_same_tiles(expected.iter(), result.tiles.iter()).map_err(|e| fail(&e))?;
+                melds.push(result.clone());
+            }
+            HumanMove::MoveTile { tile, from, remaining, to, added_tiles, result } => {
+                take_meld(&mut melds, from).map_err(|e| fail(&e))?;
+                check_same_tiles(from.tiles.iter(), remaining.tiles.iter().chain([tile])).map_err(|e| fail(&e))?;
+                if let Some(to) = to {
+                    take_meld(&mut melds, to).map_err(|e| fail(&e))?;
+                }
+                take_from_hand(&mut hand, added_tiles.iter()).map_err(|e| fail(&e))?;
+                let joined = to.iter().flat_map(|m| m.tiles.iter()).chain([tile]).chain(added_tiles);
+                check_same_tiles(joined, result.tiles.iter()).map_err(|e| fail(&e))?;
+                melds.push(remaining.clone());
+                melds.push(result.clone());
+            }
+            HumanMove::InsertAndSplit { original, inserted, parts } => {
+                take_meld(&mut melds, original).map_err(|e| fail(&e))?;
+                take_from_hand(&mut hand, inserted.iter()).map_err(|e| fail(&e))?;
+                check_same_tiles(original.tiles.iter().chain(inserted), parts.iter().flat_map(|p| p.tiles.iter()))
+                    .map_err(|e| fail(&e))?;
+                melds.extend(parts.iter().cloned());
+            }
+            HumanMove::Rearrange { consumed, produced, hand_tiles_used } => {
+                for meld in consumed {
+                    take_meld(&mut melds, meld).map_err(|e| fail(&e))?;
+                    loose.extend(meld.tiles.iter().copied());
+                }
+                take_from_hand(&mut hand, hand_tiles_used.iter()).map_err(|e| fail(&e))?;
+                loose.extend(hand_tiles_used.iter().copied());
+                for tile in produced.iter().flat_map(|m| m.tiles.iter()) {
+                    let pos = loose
+                        .iter()
+                        .position(|t| t == tile)
+                        .ok_or_else(|| fail(&format!("no {} available for the rearranged melds", tile)))?;
+                    loose.swap_remove(pos);
+                }
+                melds.extend(produced.iter().cloned());
+            }
+        }
+    }
+
+    for tile in loose {
+        hand.add(tile);
+    }
+    let mut result = Table::new();
+    for meld in melds {
+        result.add_meld(meld);
+    }
+    Ok((result, hand))
+}
+
+/// Check that human moves reproduce what the solver moves do to the table
+/// and hand; melds may end up in any order
+pub fn verify_human_moves(
+    original_table: &Table,
+    original_hand: &Hand,
+    solver_moves: &[SolverMove],
+    human_moves: &[HumanMove],
+) -> Result<(), String> {
+    let (replayed_table, replayed_hand) = apply_human_moves(original_table, original_hand, human_moves)?;
+    let mut expected_table = original_table.clone();
+    let mut expected_hand = original_hand.clone();
+    apply_moves(&mut expected_table, &mut expected_hand, solver_moves);
+
+    let sorted_melds = |table: &Table| {
+        let mut keys: Vec<(bool, Vec<Tile>)> = table
+            .melds()
+            .iter()
+            .map(|m| {
+                let mut tiles: Vec<Tile> = m.tiles.iter().copied().collect();
+                tiles.sort();
+                (m.meld_type == MeldType::Run, tiles)
+            })
+            .collect();
+        keys.sort();
+        keys
+    };
+    if sorted_melds(&replayed_table) != sorted_melds(&expected_table) {
+        return Err("Replayed table differs from the solver's final table".to_string());
+    }
+    if replayed_hand != expected_hand {
+        return Err("Replayed hand differs from the solver's final hand".to_string());
+    }
+    Ok(())
+}
+
+/// Translate solver moves into human moves, falling back to a single
+/// `Rearrange` of every picked-up meld when replaying the translation does
+/// not reproduce the solver's result
+pub fn translate_to_human_moves_checked(
+    original_table: &Table,
+    original_hand: &Hand,
+    solver_moves: &[SolverMove],
+    rules: &HouseRules,
+) -> Vec<HumanMove> {
+    let human_moves = translate_to_human_moves(original_table, original_hand, solver_moves, rules);
+    checked_or_rearrange(original_table, original_hand, solver_moves, human_moves)
+}
+
+/// Keep `human_moves` if replaying them reproduces the solver's result,
+/// otherwise describe the solver moves as a single `Rearrange`
+fn checked_or_rearrange(
+    original_table: &Table,
+    original_hand: &Hand,
+    solver_moves: &[SolverMove],
+    human_moves: Vec<HumanMove>,
+) -> Vec<HumanMove> {
+    if verify_human_moves(original_table, original_hand, solver_moves, &human_moves).is_ok() {
+        return human_moves;
+    }
+
+    let (picked_melds, laid_down_melds) = split_solver_moves(original_table, solver_moves);
+    let mut rack = original_hand.clone();
+    apply_moves(&mut original_table.clone(), &mut rack, solver_moves);
+    let hand_tiles_used = original_hand
+        .iter()
+        .flat_map(|(tile, &count)| std::iter::repeat_n(*tile, count.saturating_sub(rack.count(tile)) as usize))
+        .collect();
+    vec![HumanMove::Rearrange {
+        consumed: picked_melds.into_iter().map(|(_, meld)| meld).collect(),
+        produced: laid_down_melds,
+        hand_tiles_used,
+    }]
+}
+
+/// Remove the first meld equal to `meld` from the table
+fn take_meld(melds: &mut Vec<Meld>, meld: &Meld) -> Result<(), String> {
+    let pos = melds
+        .iter()
+        .position(|m| m == meld)
+        .ok_or_else(|| "meld not on the table".to_string())?;
+    melds.remove(pos);
+    Ok(())
+}
+
+/// Remove tiles from the hand, failing if one is missing
+fn take_from_hand<'a>(hand: &mut Hand, tiles: impl Iterator<Item = &'a Tile>) -> Result<(), String> {
+    for tile in tiles {
+        if !hand.remove(tile) {
+            return Err(format!("{} not in hand", tile));
+        }
+    }
+    Ok(())
+}
+
+/// Check that two tile lists hold the same tiles, in any order
+fn check_same_tiles<'a, 'b>(
+    a: impl Iterator<Item = &'a Tile>,
+    b: impl Iterator<Item = &'b Tile>,
+) -> Result<(), String> {
+    let mut a: Vec<Tile> = a.copied().collect();
+    let mut b: Vec<Tile> = b.copied().collect();
+    a.sort();
+    b.sort();
+    if a == b {
+        Ok(())
+    } else {
+        Err("tiles before and after the move differ".to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1706,7 +3224,7 @@ mod tests {
         hand.add(Tile::new(0, 3)); // Red 3
         hand.add(Tile::new(0, 4)); // Red 4
 
-        let melds = generate_all_valid_melds(&hand);
+        let melds = generate_all_valid_melds(&hand, &HouseRules::default());
 
         // Should generate: [1,2,3], [2,3,4], [1,2,3,4]
         assert!(melds.len() >= 3);
@@ -1720,7 +3238,7 @@ mod tests {
         hand.add(Tile::new(1, 5)); // Blue 5
         hand.add(Tile::new(2, 5)); // Yellow 5
 
-        let melds = generate_all_valid_melds(&hand);
+        let melds = generate_all_valid_melds(&hand, &HouseRules::default());
 
         // Should generate at least the group [R5, B5, Y5]
         assert!(melds.iter().any(|m| m.meld_type == MeldType::Group && m.tiles.len() == 3));
@@ -1748,7 +3266,7 @@ mod tests {
 
         let timer = TimeTracker::new(1000);
         let empty_debt = WildDebt::default();
-        let result = find_best_melds(&mut hand, quality, &hand_to_beat, &timer, &empty_debt);
+        let result = find_best_melds(&mut hand, quality, &hand_to_beat, &timer, &empty_debt, &HouseRules::default(), &PlayConstraints::default());
 
         // Should find a solution (play the run of 4)
         assert!(result.is_some());
@@ -1763,7 +3281,7 @@ mod tests {
         hand.add(Tile::new(0, 3)); // Red 3
         hand.add(Tile::wild());     // Wildcard
 
-        let melds = generate_all_valid_melds(&hand);
+        let melds = generate_all_valid_melds(&hand, &HouseRules::default());
 
         // Should generate run [R1, Wild(as R2), R3]
         assert!(melds.iter().any(|m| {
@@ -1780,7 +3298,7 @@ mod tests {
         hand.add(Tile::new(1, 5)); // Blue 5
         hand.add(Tile::wild());     // Wildcard
 
-        let melds = generate_all_valid_melds(&hand);
+        let melds = generate_all_valid_melds(&hand, &HouseRules::default());
 
         // Should generate group with wildcard
         assert!(melds.iter().any(|m| {
@@ -1829,7 +3347,7 @@ mod tests {
 
         let timer = TimeTracker::new(1000);
         let empty_debt = WildDebt::default();
-        let _result = find_best_melds(&mut hand, quality, &hand_to_beat, &timer, &empty_debt);
+        let _result = find_best_melds(&mut hand, quality, &hand_to_beat, &timer, &empty_debt, &HouseRules::default(), &PlayConstraints::default());
 
         // Hand should be unchanged regardless of result
         assert_eq!(hand, original);
@@ -1854,7 +3372,7 @@ mod tests {
 
         let timer = TimeTracker::new(1000);
         let empty_debt = WildDebt::default();
-        let result = find_best_melds(&mut hand, quality, &hand_to_beat, &timer, &empty_debt);
+        let result = find_best_melds(&mut hand, quality, &hand_to_beat, &timer, &empty_debt, &HouseRules::default(), &PlayConstraints::default());
 
         // Hand should be unchanged even when no solution is found
         assert_eq!(hand, original);
@@ -2074,7 +3592,7 @@ mod tests {
         };
         let timer = TimeTracker::new(5000);
         let empty_debt = WildDebt::default();
-        let depth0_result = find_best_melds(&mut hand, quality, &original_hand, &timer, &empty_debt);
+        let depth0_result = find_best_melds(&mut hand, quality, &original_hand, &timer, &empty_debt, &HouseRules::default(), &PlayConstraints::default());
         assert!(depth0_result.is_some(), "Depth 0 should find a solution");
         let depth0_melds = depth0_result.unwrap();
 
@@ -2171,7 +3689,7 @@ mod tests {
 
         let solver_moves = vec![SolverMove::LayDown(meld.clone())];
 
-        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves);
+        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
 
         assert_eq!(human_moves.len(), 1);
         match &human_moves[0] {
@@ -2209,7 +3727,7 @@ mod tests {
             SolverMove::LayDown(extended_meld.clone()),
         ];
 
-        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves);
+        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
 
         assert_eq!(human_moves.len(), 1);
         match &human_moves[0] {
@@ -2259,7 +3777,7 @@ mod tests {
             SolverMove::LayDown(part2.clone()),
         ];
 
-        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves);
+        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
 
         assert_eq!(human_moves.len(), 1);
         match &human_moves[0] {
@@ -2305,7 +3823,7 @@ mod tests {
             SolverMove::LayDown(combined.clone()),
         ];
 
-        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves);
+        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
 
         assert_eq!(human_moves.len(), 1);
         match &human_moves[0] {
@@ -2386,7 +3904,7 @@ mod tests {
             SolverMove::LayDown(run2),
         ];
 
-        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves);
+        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
 
         // Should produce some combination of moves
         // The exact pattern depends on analysis, but we should get something
@@ -2399,101 +3917,455 @@ mod tests {
         let hand = Hand::new();
         let solver_moves: Vec<SolverMove> = vec![];
 
-        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves);
+        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
 
         assert!(human_moves.is_empty());
     }
 
-    // ========================================================================
-    // Wild Debt Tests
-    // ========================================================================
-
     #[test]
-    fn test_compute_represented_tile_run() {
-        // Run: [R1, Wild, R3] - wild at position 1 represents R2
-        let mut tiles = VecDeque::new();
-        tiles.push_back(Tile::new(0, 1)); // Red 1
-        tiles.push_back(Tile::wild());
-        tiles.push_back(Tile::new(0, 3)); // Red 3
-        let meld = Meld::new(MeldType::Run, tiles);
+    fn test_plan_tile_actions_split() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("7 r b y").unwrap());
+        table.add_meld(Meld::from_string("r 1 2 3 4 5 6").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 4)); // R4
 
-        let represented = compute_represented_tile(&meld, 1);
-        assert_eq!(represented, Some(RepresentedTile::Concrete(Tile::new(0, 2))));
+        let solver_moves = vec![
+            SolverMove::PickUp(1),
+            SolverMove::LayDown(Meld::from_string("r 1 2 3 4").unwrap()),
+            SolverMove::LayDown(Meld::from_string("r 4 5 6").unwrap()),
+        ];
+        let plan = plan_tile_actions(&table, &hand, &solver_moves);
+
+        // The table's R4 stays with R1-R3, the rack's R4 starts the split-off R5 R6
+        assert_eq!(plan.actions[0], TileAction::Split { slot: 1, after: 4, new_slot: 2 });
+        assert_eq!(plan.tile_moves(), 1);
+        assert_eq!(plan.final_slots, vec![1, 2]);
+        assert_eq!(plan.actions[1], TileAction::PlaceFromRack { tile: Tile::new(0, 4), to: 2 });
     }
 
     #[test]
-    fn test_compute_represented_tile_run_wild_at_start() {
-        // Run: [Wild, R2, R3] - wild at position 0 represents R1
-        let mut tiles = VecDeque::new();
-        tiles.push_back(Tile::wild());
-        tiles.push_back(Tile::new(0, 2)); // Red 2
-        tiles.push_back(Tile::new(0, 3)); // Red 3
-        let meld = Meld::new(MeldType::Run, tiles);
+    fn test_plan_tile_actions_moves_between_melds() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("5 r b y k").unwrap());
+        table.add_meld(Meld::from_string("r 6 7 8").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 9)); // R9
 
-        let represented = compute_represented_tile(&meld, 0);
-        assert_eq!(represented, Some(RepresentedTile::Concrete(Tile::new(0, 1))));
+        let solver_moves = vec![
+            SolverMove::PickUp(0),
+            SolverMove::PickUp(1),
+            SolverMove::LayDown(Meld::from_string("5 b y k").unwrap()),
+            SolverMove::LayDown(Meld::from_string("r 5 6 7 8 9").unwrap()),
+        ];
+        let plan = plan_tile_actions(&table, &hand, &solver_moves);
+
+        assert_eq!(plan.final_slots, vec![0, 1]);
+        assert_eq!(
+            plan.actions,
+            vec![
+                TileAction::MoveTile { tile: Tile::new(0, 5), from: 0, to: 1 },
+                TileAction::PlaceFromRack { tile: Tile::new(0, 9), to: 1 },
+            ]
+        );
     }
 
     #[test]
-    fn test_compute_represented_tile_run_wild_at_end() {
-        // Run: [R1, R2, Wild] - wild at position 2 represents R3
-        let mut tiles = VecDeque::new();
-        tiles.push_back(Tile::new(0, 1)); // Red 1
-        tiles.push_back(Tile::new(0, 2)); // Red 2
-        tiles.push_back(Tile::wild());
-        let meld = Meld::new(MeldType::Run, tiles);
-
-        let represented = compute_represented_tile(&meld, 2);
-        assert_eq!(represented, Some(RepresentedTile::Concrete(Tile::new(0, 3))));
+    fn test_min_cost_assignment() {
+        // Greedy would give source 0 to the first destination and leave the
+        // second with its expensive option
+        let costs = vec![vec![1, 2], vec![1, 100]];
+        assert_eq!(min_cost_assignment(&costs), vec![Some(1), Some(0)]);
+
+        // More destinations than sources: one goes without
+        let costs = vec![vec![5], vec![1]];
+        assert_eq!(min_cost_assignment(&costs), vec![None, Some(0)]);
     }
 
     #[test]
-    fn test_compute_represented_tile_group_of_4() {
-        // Group of 4: [R5, B5, Y5, Wild] - wild represents K5 (the missing color)
-        let mut tiles = VecDeque::new();
-        tiles.push_back(Tile::new(0, 5)); // Red 5
-        tiles.push_back(Tile::new(1, 5)); // Blue 5
-        tiles.push_back(Tile::new(2, 5)); // Yellow 5
-        tiles.push_back(Tile::wild());
-        let meld = Meld::new(MeldType::Group, tiles);
+    fn test_translate_duplicate_tiles_keep_their_melds() {
+        // Both picked-up melds hold an R5; each must stay in the meld it extends
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("5 r b y").unwrap());
+        table.add_meld(Meld::from_string("r 5 6 7").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 4)); // R4
+        hand.add(Tile::new(3, 5)); // K5
 
-        let represented = compute_represented_tile(&meld, 3);
-        assert_eq!(represented, Some(RepresentedTile::Concrete(Tile::new(3, 5)))); // Black 5
+        let solver_moves = vec![
+            SolverMove::PickUp(0),
+            SolverMove::PickUp(1),
+            SolverMove::LayDown(Meld::from_string("r 4 5 6 7").unwrap()),
+            SolverMove::LayDown(Meld::from_string("5 r b y k").unwrap()),
+        ];
+
+        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
+
+        assert_eq!(human_moves.len(), 2);
+        assert!(
+            human_moves.iter().all(|m| matches!(m, HumanMove::ExtendMeld { added_tiles, .. } if added_tiles.len() == 1)),
+            "Expected two single-tile extensions, got {:?}",
+            human_moves
+        );
     }
 
     #[test]
-    fn test_compute_represented_tile_group_of_3() {
-        // Group of 3: [R5, B5, Wild] - wild could be Y5 or K5
-        let mut tiles = VecDeque::new();
-        tiles.push_back(Tile::new(0, 5)); // Red 5
-        tiles.push_back(Tile::new(1, 5)); // Blue 5
-        tiles.push_back(Tile::wild());
-        let meld = Meld::new(MeldType::Group, tiles);
-
-        let represented = compute_represented_tile(&meld, 2);
-        match represented {
-            Some(RepresentedTile::EitherOf(t1, t2)) => {
-                // Should be Y5 and K5 (colors 2 and 3)
-                assert!(
-                    (t1 == Tile::new(2, 5) && t2 == Tile::new(3, 5)) ||
-                    (t1 == Tile::new(3, 5) && t2 == Tile::new(2, 5))
-                );
+    fn test_human_moves_replay_to_solver_table() {
+        // Every translation of a real solve must replay to the solver's table
+        let positions: Vec<(Vec<&str>, Vec<Tile>)> = vec![
+            (vec!["r 1 2 3"], vec![Tile::new(0, 4)]),
+            (vec!["5 r b y", "r 5 6 7"], vec![Tile::new(0, 4), Tile::new(3, 5)]),
+            (vec!["r 1 2 3 4 5 6 7"], vec![Tile::new(1, 4), Tile::new(2, 4)]),
+            (vec!["r 1 w 3"], vec![Tile::new(0, 2), Tile::new(1, 1), Tile::new(2, 1)]),
+        ];
+        for (melds, tiles) in positions {
+            let mut table = Table::new();
+            for meld in &melds {
+                table.add_meld(Meld::from_string(meld).unwrap());
             }
-            _ => panic!("Expected EitherOf for group of 3 with wild"),
+            let mut hand = Hand::new();
+            for tile in tiles {
+                hand.add(tile);
+            }
+            let result = find_best_moves(&mut table.clone(), &mut hand.clone(), 2000);
+            let solver_moves = result.moves.expect("position should have a play");
+            let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
+            assert_eq!(
+                verify_human_moves(&table, &hand, &solver_moves, &human_moves),
+                Ok(()),
+                "Translation of {:?} did not replay: {:?}",
+                melds,
+                human_moves
+            );
         }
     }
 
     #[test]
-    fn test_compute_wild_debts_single_run() {
-        // Single run with wild: [R1, Wild, R3]
-        let mut tiles = VecDeque::new();
-        tiles.push_back(Tile::new(0, 1));
-        tiles.push_back(Tile::wild());
-        tiles.push_back(Tile::new(0, 3));
+    fn test_apply_human_moves_rejects_inconsistent_moves() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 1 2 3").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 4));
+
+        // Extending with a tile the hand does not hold
+        let bad = vec![HumanMove::ExtendMeld {
+            original: Meld::from_string("r 1 2 3").unwrap(),
+            added_tiles: vec![Tile::new(1, 4)],
+            result: Meld::from_string("r 1 2 3 4").unwrap(),
+        }];
+        assert!(apply_human_moves(&table, &hand, &bad).is_err());
+
+        // Splitting a meld that is not on the table
+        let bad = vec![HumanMove::SplitMeld {
+            original: Meld::from_string("b 1 2 3 4 5 6").unwrap(),
+            parts: vec![Meld::from_string("b 1 2 3").unwrap(), Meld::from_string("b 4 5 6").unwrap()],
+        }];
+        assert!(apply_human_moves(&table, &hand, &bad).is_err());
+    }
+
+    #[test]
+    fn test_checked_translation_falls_back_to_rearrange() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 1 2 3").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 4));
+        let solver_moves = vec![
+            SolverMove::PickUp(0),
+            SolverMove::LayDown(Meld::from_string("r 1 2 3 4").unwrap()),
+        ];
+
+        // A consistent translation is kept as is
+        let human_moves = translate_to_human_moves_checked(&table, &hand, &solver_moves, &HouseRules::default());
+        assert!(matches!(human_moves.as_slice(), [HumanMove::ExtendMeld { .. }]));
+
+        // A translation that claims the wrong result is replaced by one Rearrange
+        let wrong = vec![HumanMove::PlayFromHand(Meld::from_string("r 4 5 6").unwrap())];
+        assert!(verify_human_moves(&table, &hand, &solver_moves, &wrong).is_err());
+
+        let fallback = checked_or_rearrange(&table, &hand, &solver_moves, wrong);
+        assert_eq!(
+            fallback,
+            vec![HumanMove::Rearrange {
+                consumed: vec![Meld::from_string("r 1 2 3").unwrap()],
+                produced: vec![Meld::from_string("r 1 2 3 4").unwrap()],
+                hand_tiles_used: vec![Tile::new(0, 4)],
+            }]
+        );
+        assert_eq!(verify_human_moves(&table, &hand, &solver_moves, &fallback), Ok(()));
+    }
+
+    #[test]
+    fn test_translate_swap_wild_in_group() {
+        // The wild stands for Y5 or K5; swapping in K5 moves it to a new slot
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("5 r b w").unwrap());
+        table.add_meld(Meld::from_string("r 1 2 3").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(3, 5)); // K5
+
+        let solver_moves = vec![
+            SolverMove::PickUp(0),
+            SolverMove::PickUp(1),
+            SolverMove::LayDown(Meld::from_string("5 r b k").unwrap()),
+            SolverMove::LayDown(Meld::new(
+                MeldType::Run,
+                [Tile::new(0, 1), Tile::new(0, 2), Tile::new(0, 3), Tile::wild()].into(),
+            )),
+        ];
+
+        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
+
+        match &human_moves[0] {
+            HumanMove::SwapWild { swaps, .. } => assert_eq!(swaps, &vec![(Tile::new(3, 5), Tile::wild())]),
+            other => panic!("Expected SwapWild, got {:?}", other),
+        }
+        assert_eq!(verify_human_moves(&table, &hand, &solver_moves, &human_moves), Ok(()));
+    }
+
+    #[test]
+    fn test_translate_swap_wild_reused_in_new_meld() {
+        // Swap R4 for the wild, then play the wild in a group of 7s
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 3 w 5").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 4)); // R4
+        hand.add(Tile::new(1, 7)); // B7
+        hand.add(Tile::new(2, 7)); // Y7
+
+        let solver_moves = vec![
+            SolverMove::PickUp(0),
+            SolverMove::LayDown(Meld::from_string("r 3 4 5").unwrap()),
+            SolverMove::LayDown(Meld::new(
+                MeldType::Group,
+                [Tile::new(1, 7), Tile::new(2, 7), Tile::wild()].into(),
+            )),
+        ];
+
+        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
+
+        assert!(
+            matches!(&human_moves[0], HumanMove::SwapWild { swaps, .. } if swaps == &vec![(Tile::new(0, 4), Tile::wild())]),
+            "Expected SwapWild first, got {:?}",
+            human_moves
+        );
+        assert_eq!(verify_human_moves(&table, &hand, &solver_moves, &human_moves), Ok(()));
+    }
+
+    #[test]
+    fn test_translate_insert_and_split() {
+        // A second R3 splits R1-R5 into R1-R3 and R3-R5
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 1 2 3 4 5").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 3));
+
+        let solver_moves = vec![
+            SolverMove::PickUp(0),
+            SolverMove::LayDown(Meld::from_string("r 1 2 3").unwrap()),
+            SolverMove::LayDown(Meld::from_string("r 3 4 5").unwrap()),
+        ];
+
+        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
+
+        match human_moves.as_slice() {
+            [HumanMove::InsertAndSplit { inserted, parts, .. }] => {
+                assert_eq!(inserted, &vec![Tile::new(0, 3)]);
+                assert_eq!(parts.len(), 2);
+            }
+            other => panic!("Expected InsertAndSplit, got {:?}", other),
+        }
+        assert_eq!(verify_human_moves(&table, &hand, &solver_moves, &human_moves), Ok(()));
+    }
+
+    #[test]
+    fn test_translate_split_then_extend_end() {
+        // R7 only extends the end of R4-R6, so R1-R6 is split and then extended
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 1 2 3 4 5 6").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 7));
+
+        let solver_moves = vec![
+            SolverMove::PickUp(0),
+            SolverMove::LayDown(Meld::from_string("r 1 2 3").unwrap()),
+            SolverMove::LayDown(Meld::from_string("r 4 5 6 7").unwrap()),
+        ];
+
+        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
+
+        match human_moves.as_slice() {
+            [HumanMove::SplitMeld { parts, .. }, HumanMove::ExtendMeld { original, added_tiles, result }] => {
+                assert_eq!(parts.len(), 2);
+                assert!(parts.contains(original));
+                assert_eq!(original, &Meld::from_string("r 4 5 6").unwrap());
+                assert_eq!(added_tiles, &vec![Tile::new(0, 7)]);
+                assert_eq!(result, &Meld::from_string("r 4 5 6 7").unwrap());
+            }
+            other => panic!("Expected SplitMeld then ExtendMeld, got {:?}", other),
+        }
+        assert_eq!(verify_human_moves(&table, &hand, &solver_moves, &human_moves), Ok(()));
+    }
+
+    #[test]
+    fn test_translate_move_end_tile_to_new_group() {
+        // R4 leaves the run to complete a group with B4 and Y4 from hand
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 1 2 3 4").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(1, 4));
+        hand.add(Tile::new(2, 4));
+
+        let solver_moves = vec![
+            SolverMove::PickUp(0),
+            SolverMove::LayDown(Meld::from_string("r 1 2 3").unwrap()),
+            SolverMove::LayDown(Meld::from_string("4 r b y").unwrap()),
+        ];
+
+        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
+
+        match human_moves.as_slice() {
+            [HumanMove::MoveTile { tile, to: None, added_tiles, .. }] => {
+                assert_eq!(*tile, Tile::new(0, 4));
+                assert_eq!(added_tiles.len(), 2);
+            }
+            other => panic!("Expected MoveTile, got {:?}", other),
+        }
+        assert_eq!(verify_human_moves(&table, &hand, &solver_moves, &human_moves), Ok(()));
+    }
+
+    #[test]
+    fn test_translate_move_tile_between_melds() {
+        // K5 moves from the group onto the front of the black run
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("5 r b y k").unwrap());
+        table.add_meld(Meld::from_string("k 6 7 8").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(3, 9));
+
+        let solver_moves = vec![
+            SolverMove::PickUp(0),
+            SolverMove::PickUp(1),
+            SolverMove::LayDown(Meld::from_string("5 r b y").unwrap()),
+            SolverMove::LayDown(Meld::from_string("k 5 6 7 8 9").unwrap()),
+        ];
+
+        let human_moves = translate_to_human_moves(&table, &hand, &solver_moves, &HouseRules::default());
+
+        match human_moves.as_slice() {
+            [HumanMove::MoveTile { tile, to: Some(to), added_tiles, .. }] => {
+                assert_eq!(*tile, Tile::new(3, 5));
+                assert_eq!(to.tiles.len(), 3);
+                assert_eq!(added_tiles, &vec![Tile::new(3, 9)]);
+            }
+            other => panic!("Expected MoveTile, got {:?}", other),
+        }
+        assert_eq!(verify_human_moves(&table, &hand, &solver_moves, &human_moves), Ok(()));
+    }
+
+    #[test]
+    fn test_moves_report_joker_assignments() {
+        let run = Meld::from_string("r 5 6 w").unwrap();
+        let expected = vec![WildAssignment { position: 2, represents: Tile::new(0, 7) }];
+        assert_eq!(SolverMove::LayDown(run.clone()).joker_assignments(), expected);
+        assert!(SolverMove::PickUp(0).joker_assignments().is_empty());
+
+        let split = HumanMove::SplitMeld {
+            original: Meld::from_string("r 1 2 3 4 5 6 w").unwrap(),
+            parts: vec![Meld::from_string("r 1 2 3 4").unwrap(), Meld::from_string("r 5 6 w").unwrap()],
+        };
+        assert_eq!(split.joker_assignments(), vec![(&run, expected)]);
+    }
+
+    // ========================================================================
+    // Wild Debt Tests
+    // ========================================================================
+
+    #[test]
+    fn test_compute_represented_tile_run() {
+        // Run: [R1, Wild, R3] - wild at position 1 represents R2
+        let mut tiles = VecDeque::new();
+        tiles.push_back(Tile::new(0, 1)); // Red 1
+        tiles.push_back(Tile::wild());
+        tiles.push_back(Tile::new(0, 3)); // Red 3
+        let meld = Meld::new(MeldType::Run, tiles);
+
+        let represented = compute_represented_tile(&meld, 1, &HouseRules::default());
+        assert_eq!(represented, Some(RepresentedTile::Concrete(Tile::new(0, 2))));
+    }
+
+    #[test]
+    fn test_compute_represented_tile_run_wild_at_start() {
+        // Run: [Wild, R2, R3] - wild at position 0 represents R1
+        let mut tiles = VecDeque::new();
+        tiles.push_back(Tile::wild());
+        tiles.push_back(Tile::new(0, 2)); // Red 2
+        tiles.push_back(Tile::new(0, 3)); // Red 3
+        let meld = Meld::new(MeldType::Run, tiles);
+
+        let represented = compute_represented_tile(&meld, 0, &HouseRules::default());
+        assert_eq!(represented, Some(RepresentedTile::Concrete(Tile::new(0, 1))));
+    }
+
+    #[test]
+    fn test_compute_represented_tile_run_wild_at_end() {
+        // Run: [R1, R2, Wild] - wild at position 2 represents R3
+        let mut tiles = VecDeque::new();
+        tiles.push_back(Tile::new(0, 1)); // Red 1
+        tiles.push_back(Tile::new(0, 2)); // Red 2
+        tiles.push_back(Tile::wild());
+        let meld = Meld::new(MeldType::Run, tiles);
+
+        let represented = compute_represented_tile(&meld, 2, &HouseRules::default());
+        assert_eq!(represented, Some(RepresentedTile::Concrete(Tile::new(0, 3))));
+    }
+
+    #[test]
+    fn test_compute_represented_tile_group_of_4() {
+        // Group of 4: [R5, B5, Y5, Wild] - wild represents K5 (the missing color)
+        let mut tiles = VecDeque::new();
+        tiles.push_back(Tile::new(0, 5)); // Red 5
+        tiles.push_back(Tile::new(1, 5)); // Blue 5
+        tiles.push_back(Tile::new(2, 5)); // Yellow 5
+        tiles.push_back(Tile::wild());
+        let meld = Meld::new(MeldType::Group, tiles);
+
+        let represented = compute_represented_tile(&meld, 3, &HouseRules::default());
+        assert_eq!(represented, Some(RepresentedTile::Concrete(Tile::new(3, 5)))); // Black 5
+    }
+
+    #[test]
+    fn test_compute_represented_tile_group_of_3() {
+        // Group of 3: [R5, B5, Wild] - wild could be Y5 or K5
+        let mut tiles = VecDeque::new();
+        tiles.push_back(Tile::new(0, 5)); // Red 5
+        tiles.push_back(Tile::new(1, 5)); // Blue 5
+        tiles.push_back(Tile::wild());
+        let meld = Meld::new(MeldType::Group, tiles);
+
+        let represented = compute_represented_tile(&meld, 2, &HouseRules::default());
+        match represented {
+            Some(RepresentedTile::EitherOf(t1, t2)) => {
+                // Should be Y5 and K5 (colors 2 and 3)
+                assert!(
+                    (t1 == Tile::new(2, 5) && t2 == Tile::new(3, 5)) ||
+                    (t1 == Tile::new(3, 5) && t2 == Tile::new(2, 5))
+                );
+            }
+            _ => panic!("Expected EitherOf for group of 3 with wild"),
+        }
+    }
+
+    #[test]
+    fn test_compute_wild_debts_single_run() {
+        // Single run with wild: [R1, Wild, R3]
+        let mut tiles = VecDeque::new();
+        tiles.push_back(Tile::new(0, 1));
+        tiles.push_back(Tile::wild());
+        tiles.push_back(Tile::new(0, 3));
         let meld = Meld::new(MeldType::Run, tiles);
 
         let picked_melds = vec![(0, meld)];
-        let debt = compute_wild_debts(&picked_melds);
+        let debt = compute_wild_debts(&picked_melds, &HouseRules::default());
 
         assert_eq!(debt.concrete.get(&Tile::new(0, 2)), Some(&1)); // R2 is owed
         assert!(debt.either_or.is_empty());
@@ -2509,7 +4381,7 @@ mod tests {
         let meld = Meld::new(MeldType::Group, tiles);
 
         let picked_melds = vec![(0, meld)];
-        let debt = compute_wild_debts(&picked_melds);
+        let debt = compute_wild_debts(&picked_melds, &HouseRules::default());
 
         assert!(debt.concrete.is_empty());
         assert_eq!(debt.either_or.len(), 1);
@@ -2585,6 +4457,123 @@ mod tests {
         assert!(!is_wild_debt_satisfied(&debt, &[meld]));
     }
 
+    #[test]
+    fn test_compute_represented_tile_group_two_wilds() {
+        // Group of 3: [R7, Wild, Wild] - each wild is one of B7, Y7, K7
+        let meld = Meld::from_string("7 r w w").unwrap();
+
+        let represented = compute_represented_tile(&meld, 1, &HouseRules::default());
+        assert_eq!(
+            represented,
+            Some(RepresentedTile::OneOf(vec![Tile::new(1, 7), Tile::new(2, 7), Tile::new(3, 7)]))
+        );
+    }
+
+    #[test]
+    fn test_compute_represented_tile_run_two_wilds() {
+        // Run: [Wild, B5, Wild] - wilds represent B4 and B6
+        let meld = Meld::from_string("b w 5 w").unwrap();
+
+        assert_eq!(compute_represented_tile(&meld, 0, &HouseRules::default()), Some(RepresentedTile::Concrete(Tile::new(1, 4))));
+        assert_eq!(compute_represented_tile(&meld, 2, &HouseRules::default()), Some(RepresentedTile::Concrete(Tile::new(1, 6))));
+        assert_eq!(compute_represented_tile(&meld, 1, &HouseRules::default()), None); // Not a wild
+    }
+
+    #[test]
+    fn test_compute_wild_debts_run_two_wilds() {
+        let meld = Meld::from_string("b w 5 w").unwrap();
+        let debt = compute_wild_debts(&[(0, meld)], &HouseRules::default());
+
+        assert_eq!(debt.concrete.get(&Tile::new(1, 4)), Some(&1));
+        assert_eq!(debt.concrete.get(&Tile::new(1, 6)), Some(&1));
+        assert!(debt.either_or.is_empty());
+        assert!(debt.distinct.is_empty());
+    }
+
+    #[test]
+    fn test_compute_wild_debts_group_of_3_two_wilds() {
+        let meld = Meld::from_string("7 r w w").unwrap();
+        let debt = compute_wild_debts(&[(0, meld)], &HouseRules::default());
+
+        assert!(debt.concrete.is_empty());
+        assert!(debt.either_or.is_empty());
+        assert_eq!(
+            debt.distinct,
+            vec![DistinctChoice {
+                options: vec![Tile::new(1, 7), Tile::new(2, 7), Tile::new(3, 7)],
+                count: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_compute_wild_debts_group_of_4_two_wilds() {
+        // [R7, B7, Wild, Wild] - wilds must be Y7 and K7
+        let meld = Meld::from_string("7 r b w w").unwrap();
+        let debt = compute_wild_debts(&[(0, meld)], &HouseRules::default());
+
+        assert_eq!(debt.concrete.get(&Tile::new(2, 7)), Some(&1));
+        assert_eq!(debt.concrete.get(&Tile::new(3, 7)), Some(&1));
+        assert!(debt.either_or.is_empty());
+        assert!(debt.distinct.is_empty());
+    }
+
+    #[test]
+    fn test_is_wild_debt_distinct_choice() {
+        let debt = compute_wild_debts(&[(0, Meld::from_string("7 r w w").unwrap())], &HouseRules::default());
+
+        // Two different missing colors pay the debt
+        let paid = Meld::from_string("7 r b y").unwrap();
+        assert!(is_wild_debt_satisfied(&debt, &[paid]));
+
+        // A single missing color does not
+        let short = Meld::from_string("7 r y w").unwrap();
+        assert!(!is_wild_debt_satisfied(&debt, &[short]));
+
+        // Two copies of the same color do not either
+        let dup1 = Meld::from_string("7 r y w").unwrap();
+        let dup2 = Meld::from_string("7 r y w").unwrap();
+        assert!(!is_wild_debt_satisfied(&debt, &[dup1, dup2]));
+    }
+
+    #[test]
+    fn test_is_wild_debt_tiles_not_shared_between_debts() {
+        // Concrete K5 and an either-or of Y5/K5: one K5 cannot pay both
+        let mut debt = WildDebt::default();
+        debt.concrete.insert(Tile::new(3, 5), 1);
+        debt.either_or.push((Tile::new(2, 5), Tile::new(3, 5)));
+
+        let only_k5 = Meld::from_string("5 r b k").unwrap();
+        assert!(!is_wild_debt_satisfied(&debt, &[only_k5]));
+
+        let both = Meld::from_string("5 r y k").unwrap();
+        assert!(is_wild_debt_satisfied(&debt, &[both]));
+    }
+
+    #[test]
+    fn test_wild_debt_integration_two_wild_group() {
+        // Table has [R7, Wild, Wild]; player holds only Y7 for the wilds,
+        // so the group cannot be broken up to use both wilds elsewhere
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("7 r w w").unwrap());
+
+        let mut hand = Hand::new();
+        hand.add(Tile::new(2, 7)); // Y7
+        hand.add(Tile::new(1, 1)); // B1
+        hand.add(Tile::new(1, 2)); // B2
+        hand.add(Tile::new(1, 3)); // B3
+
+        let result = find_best_moves(&mut table, &mut hand, 5000);
+
+        // The blue run is played from hand; the group's wilds stay on the table
+        let moves = result.moves.expect("the blue run can be played from hand");
+        assert!(!moves.iter().any(|m| matches!(m, SolverMove::PickUp(_))));
+        assert!(moves.iter().all(|m| match m {
+            SolverMove::LayDown(meld) => !meld.tiles.iter().any(|t| t.is_wild()),
+            _ => true,
+        }));
+    }
+
     #[test]
     fn test_wild_debt_integration_with_replacement() {
         // Scenario: Table has [R1, Wild, R3], Player has [R2, B1, B2, B3]
@@ -2640,4 +4629,462 @@ mod tests {
             panic!("Should not pick up meld without replacement tile");
         }
     }
+
+    /// Laid-down melds from a solver result
+    fn laid_down(result: &SolverResult) -> Vec<Meld> {
+        result
+            .moves
+            .iter()
+            .flatten()
+            .filter_map(|m| match m {
+                SolverMove::LayDown(meld) => Some(meld.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_joker_rules_no_retrieval_from_groups_of_three() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("5 r b w").unwrap());
+
+        let mut hand = Hand::new();
+        hand.add(Tile::new(2, 5)); // Y5
+        hand.add(Tile::new(3, 1)); // K1
+        hand.add(Tile::new(3, 2)); // K2
+
+        // Built-in rules: Y5 pays for the wild, which then completes K1 K2
+        let result = find_best_moves(&mut table, &mut hand, 5000);
+        assert!(laid_down(&result).iter().any(|m| m.tiles.contains(&Tile::new(3, 1))));
+
+        // House rule: the wild stays in its group, so only Y5 can be played
+        let rules = HouseRules {
+            no_retrieval_from_groups_of_three: true,
+            ..HouseRules::default()
+        };
+        let result = find_best_moves_with_rules(&mut table, &mut hand, 5000, ScoringStrategy::MinimizeTiles, &rules);
+        let melds = laid_down(&result);
+        assert!(!melds.iter().any(|m| m.tiles.contains(&Tile::new(3, 1))));
+        assert!(melds.iter().any(|m| {
+            m.tiles.contains(&Tile::new(2, 5)) && m.tiles.contains(&Tile::wild())
+        }));
+    }
+
+    #[test]
+    fn test_joker_rules_replacement_from_hand_only() {
+        // The wild in Y8 W Y10 stands for Y9, which is available on the table
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("y 8 w 10").unwrap());
+        table.add_meld(Meld::from_string("y 9 10 11 12").unwrap());
+
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 1)); // R1
+        hand.add(Tile::new(0, 2)); // R2
+
+        // Built-in rules: Y9 moves over from the other run and frees the wild
+        let result = find_best_moves(&mut table, &mut hand, 5000);
+        assert!(laid_down(&result).iter().any(|m| m.tiles.contains(&Tile::new(0, 1))));
+
+        // House rule: the replacement must come from hand, and the hand has no Y9
+        let rules = HouseRules {
+            replacement_from_hand_only: true,
+            ..HouseRules::default()
+        };
+        let result = find_best_moves_with_rules(&mut table, &mut hand, 5000, ScoringStrategy::MinimizeTiles, &rules);
+        assert!(result.moves.is_none());
+    }
+
+    #[test]
+    fn test_joker_rules_replay_with_hand_tile() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 1 w 3").unwrap());
+        table.add_meld(Meld::from_string("k 4 5 6").unwrap());
+
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 2)); // R2
+
+        let rules = HouseRules {
+            replay_with_hand_tile: true,
+            ..HouseRules::default()
+        };
+        let result = find_best_moves_with_rules(&mut table, &mut hand, 5000, ScoringStrategy::MinimizeTiles, &rules);
+
+        // The wild may not end up in a meld made only of table tiles
+        let melds = laid_down(&result);
+        assert!(!melds.is_empty());
+        for meld in melds.iter().filter(|m| m.tiles.contains(&Tile::wild())) {
+            assert!(meld.tiles.contains(&Tile::new(0, 2)), "wild replayed without a hand tile: {:?}", meld);
+        }
+    }
+
+    #[test]
+    fn test_is_wild_debt_keep_intact() {
+        let group = Meld::from_string("5 r b w").unwrap();
+        let rules = HouseRules {
+            no_retrieval_from_groups_of_three: true,
+            ..HouseRules::default()
+        };
+        let debt = compute_wild_debts(&[(0, group)], &rules);
+        assert_eq!(debt.retrieved_wilds, 0);
+
+        let extended = Meld::from_string("5 r b y w").unwrap();
+        assert!(is_wild_debt_satisfied(&debt, &[extended]));
+
+        let broken = Meld::from_string("5 r b y").unwrap();
+        assert!(!is_wild_debt_satisfied(&debt, &[broken]));
+    }
+
+    #[test]
+    fn test_generate_runs_with_color_joker() {
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 5)); // R5
+        hand.add(Tile::new(0, 6)); // R6
+        hand.add(Tile::color_joker(1)); // Blue-only joker
+
+        let melds = generate_all_valid_melds(&hand, &HouseRules::default());
+        assert!(!melds.iter().any(|m| m.tiles.contains(&Tile::color_joker(1))));
+
+        hand.add(Tile::color_joker(0)); // Red-only joker
+        let melds = generate_all_valid_melds(&hand, &HouseRules::default());
+        assert!(melds.iter().any(|m| m.meld_type == MeldType::Run && m.tiles.contains(&Tile::color_joker(0))));
+        assert!(!melds.iter().any(|m| m.tiles.contains(&Tile::color_joker(1))));
+    }
+
+    #[test]
+    fn test_generate_groups_with_color_joker() {
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 7)); // R7
+        hand.add(Tile::new(1, 7)); // B7
+        hand.add(Tile::color_joker(0)); // Red-only joker: red is taken
+
+        let melds = generate_all_valid_melds(&hand, &HouseRules::default());
+        assert!(!melds.iter().any(|m| m.meld_type == MeldType::Group));
+
+        hand.remove(&Tile::color_joker(0));
+        hand.add(Tile::color_joker(2)); // Yellow-only joker fits
+        let melds = generate_all_valid_melds(&hand, &HouseRules::default());
+        assert!(melds.iter().any(|m| m.meld_type == MeldType::Group && m.tiles.contains(&Tile::color_joker(2))));
+    }
+
+    #[test]
+    fn test_find_best_moves_two_distinct_jokers() {
+        let mut table = Table::new();
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 1)); // R1
+        hand.add(Tile::new(0, 2)); // R2
+        hand.add(Tile::new(1, 5)); // B5
+        hand.add(Tile::new(1, 6)); // B6
+        hand.add(Tile::joker(1));
+        hand.add(Tile::joker(2));
+
+        let result = find_best_moves(&mut table, &mut hand, 5000);
+
+        // Each joker completes one of the two runs
+        let melds = laid_down(&result);
+        assert_eq!(melds.len(), 2);
+        assert_eq!(melds.iter().map(|m| m.tiles.len()).sum::<usize>(), 6);
+    }
+
+    #[test]
+    fn test_wild_debt_color_joker_in_group() {
+        // [R7, B7, Yellow-only joker]: the joker is owed as Y7, not "Y7 or K7"
+        let meld = Meld::from_string("7 r b wy").unwrap();
+        let debt = compute_wild_debts(&[(0, meld)], &HouseRules::default());
+
+        assert_eq!(debt.concrete.get(&Tile::new(2, 7)), Some(&1));
+        assert!(debt.either_or.is_empty());
+        assert!(debt.distinct.is_empty());
+    }
+
+    #[test]
+    fn test_generate_twist_melds() {
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 5)); // R5
+        hand.add(Tile::new(0, 6)); // R6
+        hand.add(Tile::twist(TwistPower::Duplicate));
+        hand.add(Tile::twist(TwistPower::ColorChange));
+        hand.add(Tile::twist(TwistPower::Mirror));
+        hand.add(Tile::new(1, 8)); // B8
+
+        let melds = generate_all_valid_melds(&hand, &HouseRules::default());
+        let has = |input: &str| {
+            let expected = Meld::from_string(input).unwrap();
+            melds.iter().any(|m| m.tiles == expected.tiles)
+        };
+        assert!(has("r 5 wd 6"));
+        assert!(has("r 5 6 wd"));
+        assert!(has("r 5 6 wc b8"));
+        assert!(!has("r 5 6 wm 6")); // Only one R6 in hand
+        assert!(melds.iter().all(|m| m.is_valid()));
+
+        // Twist jokers never stand in for ordinary jokers
+        assert!(!melds.iter().any(|m| m.tiles.iter().filter(|t| t.is_wild()).count() > 1));
+
+        hand.add(Tile::new(0, 6)); // Second R6
+        let melds = generate_all_valid_melds(&hand, &HouseRules::default());
+        let mirror = Meld::from_string("r 5 6 wm 6").unwrap();
+        assert!(melds.iter().any(|m| m.tiles == mirror.tiles));
+    }
+
+    #[test]
+    fn test_find_best_moves_with_twist_joker() {
+        let mut table = Table::new();
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 4)); // R4
+        hand.add(Tile::new(0, 5)); // R5
+        hand.add(Tile::twist(TwistPower::ColorChange));
+        hand.add(Tile::new(1, 7)); // B7
+        hand.add(Tile::new(1, 8)); // B8
+
+        let result = find_best_moves(&mut table, &mut hand, 5000);
+
+        let melds = laid_down(&result);
+        assert_eq!(melds.len(), 1);
+        assert_eq!(melds[0].tiles.len(), 5);
+    }
+
+    #[test]
+    fn test_wild_debt_twist_meld_kept_intact() {
+        let meld = Meld::from_string("r 4 5 wd 6").unwrap();
+        let debt = compute_wild_debts(&[(0, meld.clone())], &HouseRules::default());
+
+        assert_eq!(debt.keep_intact, vec![meld]);
+        assert_eq!(debt.retrieved_wilds, 0);
+        assert!(debt.concrete.is_empty());
+    }
+
+    #[test]
+    fn test_generate_wrapped_runs() {
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 12)); // R12
+        hand.add(Tile::new(0, 13)); // R13
+        hand.add(Tile::new(0, 1)); // R1
+
+        let wrapped = Meld::from_string("r 12 13 1").unwrap();
+        let melds = generate_all_valid_melds(&hand, &HouseRules::default());
+        assert!(!melds.iter().any(|m| m.tiles == wrapped.tiles));
+
+        let rules = HouseRules {
+            wrap_around_runs: true,
+            ..HouseRules::default()
+        };
+        let melds = generate_all_valid_melds(&hand, &rules);
+        assert!(melds.iter().any(|m| m.tiles == wrapped.tiles));
+        assert!(melds.iter().all(|m| m.is_valid_with_wrap(true)));
+    }
+
+    #[test]
+    fn test_find_best_moves_wrap_around_runs() {
+        let mut table = Table::new();
+        let mut hand = Hand::new();
+        hand.add(Tile::new(1, 12)); // B12
+        hand.add(Tile::new(1, 13)); // B13
+        hand.add(Tile::new(1, 1)); // B1
+        hand.add(Tile::new(1, 2)); // B2
+
+        let rules = HouseRules {
+            wrap_around_runs: true,
+            ..HouseRules::default()
+        };
+        let result = find_best_moves_with_rules(&mut table, &mut hand, 5000, ScoringStrategy::MinimizeTiles, &rules);
+        let melds = laid_down(&result);
+        assert_eq!(melds.len(), 1);
+        assert_eq!(melds[0].tiles.len(), 4);
+
+        let result = find_best_moves(&mut table, &mut hand, 5000);
+        assert!(result.moves.is_none());
+    }
+
+    #[test]
+    fn test_compute_represented_tile_wrapped_run() {
+        let rules = HouseRules {
+            wrap_around_runs: true,
+            ..HouseRules::default()
+        };
+        let meld = Meld::from_string("k 12 w 1").unwrap();
+        assert_eq!(compute_represented_tile(&meld, 1, &rules), Some(RepresentedTile::Concrete(Tile::new(3, 13))));
+        assert_eq!(compute_represented_tile(&meld, 1, &HouseRules::default()), None);
+
+        let meld = Meld::from_string("k 13 w 2").unwrap();
+        assert_eq!(compute_represented_tile(&meld, 1, &rules), Some(RepresentedTile::Concrete(Tile::new(3, 1))));
+        assert_eq!(compute_represented_tile(&meld, 1, &HouseRules::default()), None);
+    }
+
+    #[test]
+    fn test_tiles_played() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 1 2 3").unwrap());
+
+        let moves = vec![
+            SolverMove::PickUp(0),
+            SolverMove::LayDown(Meld::from_string("r 1 2 3 4").unwrap()),
+            SolverMove::LayDown(Meld::from_string("5 r b k").unwrap()),
+        ];
+        assert_eq!(tiles_played(&table, &moves), 4);
+        assert_eq!(tiles_played(&table, &[]), 0);
+    }
+
+    #[test]
+    fn test_apply_moves() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 1 2 3").unwrap());
+        table.add_meld(Meld::from_string("5 r b k").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 4)); // R4
+        hand.add(Tile::new(2, 9)); // Y9
+
+        let moves = vec![
+            SolverMove::PickUp(0),
+            SolverMove::LayDown(Meld::from_string("r 1 2 3 4").unwrap()),
+        ];
+        apply_moves(&mut table, &mut hand, &moves);
+
+        assert_eq!(table.len(), 2);
+        assert!(table.melds().contains(&Meld::from_string("r 1 2 3 4").unwrap()));
+        assert_eq!(hand.iter().map(|(t, &c)| (*t, c)).collect::<Vec<_>>(), vec![(Tile::new(2, 9), 1)]);
+    }
+
+    #[test]
+    fn test_keep_options_holds_back_a_meld() {
+        let mut table = Table::new();
+        let mut hand = Hand::new();
+        for tile in ["r1", "r2", "r3", "b7", "y7", "k7", "y12"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+
+        let result = find_best_moves_with_strategy(&mut table, &mut hand, 1000, ScoringStrategy::KeepOptions);
+        let moves = result.moves.expect("should find a play");
+
+        assert_eq!(moves.len(), 1, "one meld is played, the other held back");
+        assert_eq!(tiles_played(&table, &moves), 3);
+        let reserve: Vec<_> = result.held_back.iter().filter(|h| h.reason == HoldReason::ReserveMeld).collect();
+        assert_eq!(reserve.len(), 3);
+        assert!(!result.held_back.iter().any(|h| h.tile == Tile::from_string("y12").unwrap()));
+    }
+
+    #[test]
+    fn test_keep_options_goes_out_when_it_can() {
+        let mut table = Table::new();
+        let mut hand = Hand::new();
+        for tile in ["r1", "r2", "r3", "b7", "y7", "k7"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+
+        let result = find_best_moves_with_strategy(&mut table, &mut hand, 1000, ScoringStrategy::KeepOptions);
+
+        assert_eq!(tiles_played(&table, &result.moves.unwrap()), 6);
+        assert!(result.held_back.is_empty());
+    }
+
+    #[test]
+    fn test_explain_held_tiles() {
+        let mut rack = Hand::new();
+        for tile in ["r5", "b5", "k9", "k11", "y1", "w"] {
+            rack.add(Tile::from_string(tile).unwrap());
+        }
+
+        let held = explain_held_tiles(&rack, &HouseRules::default());
+        let reason = |s: &str| held.iter().find(|h| h.tile == Tile::from_string(s).unwrap()).map(|h| h.reason);
+
+        // r5 b5 w is the longest meld on the rack
+        assert_eq!(reason("r5"), Some(HoldReason::ReserveMeld));
+        assert_eq!(reason("w"), Some(HoldReason::ReserveMeld));
+        assert_eq!(reason("k9"), Some(HoldReason::NearRun));
+        assert_eq!(reason("y1"), None);
+    }
+
+    #[test]
+    fn test_solve_status() {
+        let mut table = Table::new();
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 1)); // R1
+        hand.add(Tile::new(1, 5)); // B5
+
+        let result = find_best_moves(&mut table, &mut hand, 1000);
+        assert_eq!(result.status(), SolveStatus::Infeasible);
+
+        hand.add(Tile::new(1, 6)); // B6
+        hand.add(Tile::new(1, 7)); // B7
+        let result = find_best_moves(&mut table, &mut hand, 1000);
+        assert_eq!(result.status(), SolveStatus::Solved);
+
+        let timed_out = SolverResult { moves: None, search_completed: false, ..result };
+        assert_eq!(timed_out.status(), SolveStatus::TimedOut);
+    }
+
+    #[test]
+    fn test_constraints_locked_tiles_stay() {
+        let mut table = Table::new();
+        let mut hand = Hand::new();
+        for tile in ["r1", "r2", "r3", "b7", "y7", "k7"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+        let mut constraints = PlayConstraints::default();
+        constraints.locked.add(Tile::from_string("b7").unwrap());
+
+        let result = find_best_moves_with_constraints(
+            &mut table, &mut hand, 1000, ScoringStrategy::MinimizeTiles, &HouseRules::default(), &constraints,
+        );
+        let moves = result.moves.unwrap();
+
+        assert_eq!(moves, vec![SolverMove::LayDown(Meld::from_string("r 1 2 3").unwrap())]);
+    }
+
+    #[test]
+    fn test_constraints_must_play() {
+        let mut table = Table::new();
+        let mut hand = Hand::new();
+        for tile in ["r1", "r2", "r3", "y3", "k3"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+        let mut constraints = PlayConstraints::default();
+        constraints.must_play.add(Tile::from_string("y3").unwrap());
+
+        let result = find_best_moves_with_constraints(
+            &mut table, &mut hand, 1000, ScoringStrategy::MinimizeTiles, &HouseRules::default(), &constraints,
+        );
+
+        assert_eq!(result.moves.unwrap(), vec![SolverMove::LayDown(Meld::from_string("3 r y k").unwrap())]);
+    }
+
+    #[test]
+    fn test_constraints_frozen_meld() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 1 2 3").unwrap());
+        let mut hand = Hand::new();
+        hand.add(Tile::new(0, 4)); // R4
+
+        let constraints = PlayConstraints { frozen_melds: vec![0], ..PlayConstraints::default() };
+        let result = find_best_moves_with_constraints(
+            &mut table, &mut hand, 1000, ScoringStrategy::MinimizeTiles, &HouseRules::default(), &constraints,
+        );
+
+        assert_eq!(result.status(), SolveStatus::Infeasible);
+        assert!(find_best_moves(&mut table, &mut hand, 1000).moves.is_some());
+    }
+
+    #[test]
+    fn test_find_moves_playing_lays_the_tile_down() {
+        let mut table = Table::new();
+        table.add_meld(Meld::from_string("r 1 2 3").unwrap());
+        let mut hand = Hand::new();
+        for tile in ["r4", "b9", "y9", "k9", "b12"] {
+            hand.add(Tile::from_string(tile).unwrap());
+        }
+        let rules = HouseRules::default();
+
+        // The first play found lays R4 down, with no need to look for more
+        let r4 = Tile::from_string("r4").unwrap();
+        let result = find_moves_playing(&mut table, &mut hand, r4, 1000, &rules);
+        assert!(result.search_completed);
+        let moves = result.moves.unwrap();
+        let mut rack = hand.clone();
+        apply_moves(&mut table.clone(), &mut rack, &moves);
+        assert_eq!(rack.count(&r4), 0);
+
+        // The 9s can be played, but no play lays B12 down
+        let result = find_moves_playing(&mut table, &mut hand, Tile::from_string("b12").unwrap(), 1000, &rules);
+        assert!(result.search_completed);
+        assert!(result.moves.is_none());
+    }
 }
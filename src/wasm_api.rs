@@ -1,4 +1,4 @@
-use crate::{Hand, Meld, MeldType, Table, Tile, solver};
+use crate::{Hand, Meld, MeldType, Table, Tile, TileSet, analysis, okey, solver};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use wasm_bindgen::prelude::*;
@@ -9,14 +9,33 @@ pub fn init() {
     console_error_panic_hook::set_once();
 }
 
-/// JSON-serializable representation of a meld
+/// JSON-serializable representation of a meld.
+/// `jokers` lists what each wild is played as; it is output only and
+/// ignored when a meld is read.
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum MeldJson {
     #[serde(rename = "group")]
-    Group { tiles: Vec<String> },
+    Group {
+        tiles: Vec<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        jokers: Vec<JokerJson>,
+    },
     #[serde(rename = "run")]
-    Run { tiles: Vec<String> },
+    Run {
+        tiles: Vec<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        jokers: Vec<JokerJson>,
+    },
+}
+
+/// JSON representation of the tile a wild is played as
+#[derive(Serialize, Deserialize)]
+pub struct JokerJson {
+    /// Index of the wild in the meld's `tiles`
+    pub position: usize,
+    /// Tile string the wild stands for (e.g., "r7")
+    pub represents: String,
 }
 
 /// JSON-serializable representation of a solver move
@@ -47,6 +66,50 @@ pub struct SolverResult {
     pub initial_quality: i32,
     /// Final hand quality after applying the solution
     pub final_quality: i32,
+    /// Tiles kept on the rack on purpose (keep_options strategy only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub held_back: Vec<HeldTileJson>,
+    /// "solved", "infeasible" (no legal play exists) or "timed_out"
+    #[serde(default)]
+    pub status: String,
+    /// Melds the hand is one tile short of, when no legal play exists and the
+    /// player set no constraints
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub near_plays: Vec<NearPlayJson>,
+    /// Ordered physical steps carrying out the moves
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<TileActionJson>,
+}
+
+/// JSON representation of one physical step. Slots below the table length
+/// are the original table melds; higher slots are melds created on the way.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum TileActionJson {
+    #[serde(rename = "split")]
+    Split { slot: usize, after: usize, new_slot: usize },
+    #[serde(rename = "move_tile")]
+    MoveTile { tile: String, from: usize, to: usize },
+    #[serde(rename = "place_from_rack")]
+    PlaceFromRack { tile: String, to: usize },
+    #[serde(rename = "take_to_rack")]
+    TakeToRack { tile: String, from: usize },
+}
+
+/// A meld the hand is one tile short of
+#[derive(Serialize, Deserialize)]
+pub struct NearPlayJson {
+    pub meld: MeldJson,
+    pub missing: String,
+    pub unseen: u8,
+}
+
+/// A tile kept on the rack and why
+#[derive(Serialize, Deserialize)]
+pub struct HeldTileJson {
+    pub tile: String,
+    /// One of "reserve_meld", "wild", "pair", "near_run"
+    pub reason: String,
 }
 
 /// JSON-serializable representation of a human-readable move
@@ -89,6 +152,23 @@ pub enum HumanMoveJson {
         result: MeldJson,
     },
 
+    #[serde(rename = "move_tile")]
+    MoveTile {
+        tile: String,
+        from: MeldJson,
+        remaining: MeldJson,
+        to: Option<MeldJson>,
+        added_tiles: Vec<String>,
+        result: MeldJson,
+    },
+
+    #[serde(rename = "insert_and_split")]
+    InsertAndSplit {
+        original: MeldJson,
+        inserted: Vec<String>,
+        parts: Vec<MeldJson>,
+    },
+
     #[serde(rename = "rearrange")]
     Rearrange {
         consumed: Vec<MeldJson>,
@@ -104,12 +184,60 @@ pub struct SwapJson {
     pub wild_taken: String,
 }
 
+/// JSON representation of the house rules (joker retrieval and run wrap-around).
+/// Missing fields default to off (the built-in behavior).
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct HouseRulesJson {
+    pub replay_with_hand_tile: bool,
+    pub no_retrieval_from_groups_of_three: bool,
+    pub replacement_from_hand_only: bool,
+    pub wrap_around_runs: bool,
+}
+
+/// JSON representation of a tile set
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct TileSetJson {
+    pub copies_per_tile: u8,
+    pub jokers: u8,
+}
+
+impl Default for TileSetJson {
+    fn default() -> Self {
+        let standard = TileSet::standard();
+        TileSetJson {
+            copies_per_tile: standard.copies_per_tile,
+            jokers: standard.jokers,
+        }
+    }
+}
+
+/// JSON representation of a tile found more often than the tile set holds
+#[derive(Serialize, Deserialize)]
+pub struct TileExcessJson {
+    /// Tile string: a special joker by its own name, or "w" for jokers of any kind
+    pub tile: String,
+    pub found: u8,
+    pub available: u8,
+}
+
+/// Result of a tile conservation check
+#[derive(Serialize, Deserialize)]
+pub struct ConservationResult {
+    pub success: bool,
+    /// Tiles over their available copies; empty when the input is consistent
+    pub excess: Vec<TileExcessJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// Main WASM API: Solve a Rummikub game state
 ///
 /// # Arguments
 /// * `hand_tiles` - JSON array of tile strings (e.g., ["r1", "b5", "w"])
 /// * `table_melds` - JSON array of meld objects (e.g., [{"type": "run", "tiles": ["r1", "r2", "r3"]}])
-/// * `strategy` - Scoring strategy: "minimize_tiles" or "minimize_points"
+/// * `strategy` - Scoring strategy: "minimize_tiles", "minimize_points" or "keep_options"
 /// * `time_limit_ms` - Maximum time to search in milliseconds
 ///
 /// # Returns
@@ -121,7 +249,53 @@ pub fn solve_rummikub(
     strategy: &str,
     time_limit_ms: u64,
 ) -> String {
-    match solve_internal(hand_tiles, table_melds, strategy, time_limit_ms) {
+    solve_rummikub_with_rules(hand_tiles, table_melds, strategy, time_limit_ms, "{}")
+}
+
+/// Solve a Rummikub game state under joker retrieval house rules
+///
+/// # Arguments
+/// * `hand_tiles`, `table_melds`, `strategy`, `time_limit_ms` - As for `solve_rummikub`
+/// * `house_rules` - JSON object of rule flags (e.g., {"replacement_from_hand_only": true})
+///
+/// # Returns
+/// JSON string with SolverResult containing success, moves, or error
+#[wasm_bindgen]
+pub fn solve_rummikub_with_rules(
+    hand_tiles: &str,
+    table_melds: &str,
+    strategy: &str,
+    time_limit_ms: u64,
+    house_rules: &str,
+) -> String {
+    solve_rummikub_with_constraints(hand_tiles, table_melds, strategy, time_limit_ms, house_rules, "{}", "{}")
+}
+
+/// Solve a Rummikub game state under house rules and the player's constraints
+///
+/// # Arguments
+/// * `hand_tiles`, `table_melds`, `strategy`, `time_limit_ms`, `house_rules` -
+///   As for `solve_rummikub_with_rules`
+/// * `constraints` - JSON object with `locked` (hand tiles that must stay on
+///   the rack), `must_play` (hand tiles that must be laid down) and
+///   `frozen_melds` (indices of table melds that must not be touched), e.g.
+///   {"locked": ["w"], "frozen_melds": [2]}
+/// * `tile_set` - JSON object with `copies_per_tile` and `jokers` ("{}" for the
+///   standard 106 tiles)
+///
+/// # Returns
+/// JSON string with SolverResult containing success, moves, or error
+#[wasm_bindgen]
+pub fn solve_rummikub_with_constraints(
+    hand_tiles: &str,
+    table_melds: &str,
+    strategy: &str,
+    time_limit_ms: u64,
+    house_rules: &str,
+    constraints: &str,
+    tile_set: &str,
+) -> String {
+    match solve_internal(hand_tiles, table_melds, strategy, time_limit_ms, house_rules, constraints, tile_set) {
         Ok(result) => serde_json::to_string(&result)
             .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Serialization error: {}"}}"#, e)),
         Err(e) => serde_json::to_string(&SolverResult {
@@ -133,6 +307,10 @@ pub fn solve_rummikub(
             depth_reached: 0,
             initial_quality: 0,
             final_quality: 0,
+            held_back: Vec::new(),
+            status: String::new(),
+            near_plays: Vec::new(),
+            steps: Vec::new(),
         })
         .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Serialization error: {}"}}"#, e)),
     }
@@ -144,75 +322,1247 @@ fn solve_internal(
     table_melds: &str,
     strategy_str: &str,
     time_limit_ms: u64,
+    house_rules: &str,
+    constraints: &str,
+    tile_set: &str,
 ) -> Result<SolverResult, String> {
-    // 1. Parse hand_tiles JSON into Vec<String>
-    let hand_strs: Vec<String> =
-        serde_json::from_str(hand_tiles).map_err(|e| format!("Invalid hand JSON: {}", e))?;
+    // 1. Parse hand_tiles JSON into a Hand
+    let mut hand = hand_from_json(hand_tiles)?;
 
-    // 2. Parse each tile string into Tile
-    let mut hand = Hand::new();
-    for tile_str in hand_strs {
-        let tile = Tile::from_string(&tile_str)?;
-        hand.add(tile);
-    }
+    // 2. Parse table_melds JSON
+    let mut table = table_from_json(table_melds)?;
 
-    // 3. Parse table_melds JSON
-    let table_json: Vec<MeldJson> =
-        serde_json::from_str(table_melds).map_err(|e| format!("Invalid table JSON: {}", e))?;
+    // 3. Parse strategy
+    let strategy = strategy_from_str(strategy_str)?;
 
-    let mut table = Table::new();
-    for meld_json in table_json {
-        let meld = meld_from_json(meld_json)?;
-        table.add_meld(meld);
-    }
+    // 4. Parse house rules and the tile set
+    let rules = rules_from_json(house_rules)?;
+    let tile_set = tile_set_from_str(tile_set)?;
 
-    // 4. Parse strategy
-    let strategy = match strategy_str {
-        "minimize_tiles" => solver::ScoringStrategy::MinimizeTiles,
-        "minimize_points" => solver::ScoringStrategy::MinimizePoints,
-        _ => return Err(format!("Unknown strategy: {}", strategy_str)),
-    };
+    // 5. Reject input holding more copies of a tile than the tile set has
+    tile_set.check(&hand, &table)?;
+
+    // 6. Parse the player's constraints
+    let constraints = constraints_from_json(constraints, &hand, &table)?;
 
     // Save original state for human move translation
     let original_table = table.clone();
     let original_hand = hand.clone();
 
-    // 5. Call solver with strategy
-    let solver_result =
-        solver::find_best_moves_with_strategy(&mut table, &mut hand, time_limit_ms, strategy);
+    // 7. Call solver with strategy, rules and constraints
+    let solver_result = solver::find_best_moves_with_constraints(
+        &mut table,
+        &mut hand,
+        time_limit_ms,
+        strategy,
+        &rules,
+        &constraints,
+    );
 
-    // 6. Convert result to JSON
+    // 8. Convert result to JSON
     let moves_json = solver_result.moves.as_ref().map(|moves| {
         moves.iter().map(|m| move_to_json(m.clone())).collect()
     });
 
-    // 7. Translate to human-readable moves
+    // 9. Translate to human-readable moves
     let human_moves_json = solver_result.moves.as_ref().map(|moves| {
-        let human_moves = solver::translate_to_human_moves(&original_table, &original_hand, moves);
+        let human_moves = solver::translate_to_human_moves_checked(&original_table, &original_hand, moves, &rules);
         human_moves.iter().map(human_move_to_json).collect()
     });
 
+    // 10. Spell the moves out as physical steps
+    let steps = solver_result.moves.as_ref().map_or_else(Vec::new, |moves| {
+        solver::plan_tile_actions(&original_table, &original_hand, moves)
+            .actions
+            .iter()
+            .map(tile_action_to_json)
+            .collect()
+    });
+
+    // 11. Explain a missing solution: nothing legal, or out of time. When the
+    // player's constraints rule the plays out, the hand's shape is not the cause.
+    let status = solver_result.status();
+    let constrained = constraints != solver::PlayConstraints::default();
+    let near_plays = if status == solver::SolveStatus::Infeasible && !constrained {
+        let unseen = tile_set.unseen(&original_hand, &original_table, &Hand::new());
+        analysis::near_plays(&original_hand, &unseen, &rules)
+            .iter()
+            .map(|play| NearPlayJson {
+                meld: meld_to_json(&play.meld),
+                missing: play.missing.to_string(),
+                unseen: play.unseen,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
     Ok(SolverResult {
         success: solver_result.moves.is_some(),
         moves: moves_json,
         human_moves: human_moves_json,
-        error: if solver_result.moves.is_none() {
-            Some("No solution found within time limit".to_string())
-        } else {
-            None
+        error: match status {
+            solver::SolveStatus::Solved => None,
+            solver::SolveStatus::Infeasible if constrained => {
+                Some("No legal play meets your constraints".to_string())
+            }
+            solver::SolveStatus::Infeasible => Some("No legal play exists".to_string()),
+            solver::SolveStatus::TimedOut => Some("No solution found within time limit".to_string()),
         },
         search_completed: solver_result.search_completed,
         depth_reached: solver_result.depth_reached,
         initial_quality: solver_result.initial_quality,
         final_quality: solver_result.final_quality,
+        held_back: solver_result.held_back.iter().map(held_tile_to_json).collect(),
+        status: match status {
+            solver::SolveStatus::Solved => "solved",
+            solver::SolveStatus::Infeasible => "infeasible",
+            solver::SolveStatus::TimedOut => "timed_out",
+        }
+        .to_string(),
+        near_plays,
+        steps,
+    })
+}
+
+/// Check hand and table tiles against the full tile set
+///
+/// Used to catch misread photos before solving, e.g. three copies of r5.
+///
+/// # Arguments
+/// * `hand_tiles` - JSON array of tile strings (e.g., ["r1", "b5", "w"])
+/// * `table_melds` - JSON array of meld objects (e.g., [{"type": "run", "tiles": ["r1", "r2", "r3"]}])
+/// * `tile_set` - JSON object with `copies_per_tile` and `jokers` ("{}" for the standard 106 tiles)
+///
+/// # Returns
+/// JSON string with ConservationResult listing the tiles over their available copies
+#[wasm_bindgen]
+pub fn check_tile_conservation(hand_tiles: &str, table_melds: &str, tile_set: &str) -> String {
+    let result = conservation_internal(hand_tiles, table_melds, tile_set).unwrap_or_else(|e| ConservationResult {
+        success: false,
+        excess: Vec::new(),
+        error: Some(e),
+    });
+    serde_json::to_string(&result)
+        .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Serialization error: {}"}}"#, e))
+}
+
+/// Internal implementation of check_tile_conservation
+fn conservation_internal(hand_tiles: &str, table_melds: &str, tile_set: &str) -> Result<ConservationResult, String> {
+    let hand = hand_from_json(hand_tiles)?;
+    let table = table_from_json(table_melds)?;
+
+    let excess = tile_set_from_str(tile_set)?
+        .excess(&hand, &table)
+        .into_iter()
+        .map(|e| TileExcessJson {
+            tile: e.tile.to_string(),
+            found: e.found,
+            available: e.available,
+        })
+        .collect();
+
+    Ok(ConservationResult {
+        success: true,
+        excess,
+        error: None,
+    })
+}
+
+/// Result of an unseen tile pool computation
+#[derive(Serialize, Deserialize)]
+pub struct UnseenResult {
+    pub success: bool,
+    /// Unseen tile strings, one entry per copy; jokers of any kind as "w"
+    pub tiles: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Compute the tiles still unseen: in the pool or in opponents' racks
+///
+/// # Arguments
+/// * `hand_tiles` - JSON array of tile strings (e.g., ["r1", "b5", "w"])
+/// * `table_melds` - JSON array of meld objects (e.g., [{"type": "run", "tiles": ["r1", "r2", "r3"]}])
+/// * `known_tiles` - JSON array of tiles known to be in other racks ("[]" if none)
+/// * `tile_set` - JSON object with `copies_per_tile` and `jokers` ("{}" for the standard 106 tiles)
+///
+/// # Returns
+/// JSON string with UnseenResult
+#[wasm_bindgen]
+pub fn unseen_tiles(hand_tiles: &str, table_melds: &str, known_tiles: &str, tile_set: &str) -> String {
+    let result = unseen_internal(hand_tiles, table_melds, known_tiles, tile_set).unwrap_or_else(|e| UnseenResult {
+        success: false,
+        tiles: Vec::new(),
+        error: Some(e),
+    });
+    serde_json::to_string(&result)
+        .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Serialization error: {}"}}"#, e))
+}
+
+/// Internal implementation of unseen_tiles
+fn unseen_internal(
+    hand_tiles: &str,
+    table_melds: &str,
+    known_tiles: &str,
+    tile_set: &str,
+) -> Result<UnseenResult, String> {
+    let hand = hand_from_json(hand_tiles)?;
+    let table = table_from_json(table_melds)?;
+    let known = hand_from_json(known_tiles)?;
+
+    let unseen = tile_set_from_str(tile_set)?.unseen(&hand, &table, &known);
+    let tiles = unseen
+        .iter()
+        .flat_map(|(tile, &count)| std::iter::repeat_n(tile.to_string(), count as usize))
+        .collect();
+
+    Ok(UnseenResult {
+        success: true,
+        tiles,
+        error: None,
+    })
+}
+
+/// Convert JSON tile set to internal TileSet type
+fn tile_set_from_json(tile_set: TileSetJson) -> TileSet {
+    TileSet {
+        copies_per_tile: tile_set.copies_per_tile,
+        jokers: tile_set.jokers,
+    }
+}
+
+/// JSON representation of what drawing one unseen tile kind would do
+#[derive(Serialize, Deserialize)]
+pub struct DrawOutcomeJson {
+    pub tile: String,
+    pub unseen: u8,
+    pub can_play: bool,
+    pub tiles_played: usize,
+    pub goes_out: bool,
+}
+
+/// Result of a draw outlook analysis
+#[derive(Serialize, Deserialize, Default)]
+pub struct DrawOutlookResult {
+    pub success: bool,
+    pub outcomes: Vec<DrawOutcomeJson>,
+    /// Hand tiles the best play lays down without drawing
+    pub baseline_played: usize,
+    /// Chance the next draw lets the player lay down more tiles
+    pub useful_probability: f64,
+    /// Chance the next draw lets the player go out
+    pub out_probability: f64,
+    pub search_completed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Report, for each unseen tile kind, what drawing it would let the player do
+///
+/// # Arguments
+/// * `hand_tiles`, `table_melds`, `strategy`, `house_rules` - As for `solve_rummikub_with_rules`
+/// * `known_tiles` - JSON array of tiles known to be in other racks ("[]" if none)
+/// * `time_limit_ms` - Total time for the analysis, shared between the solver runs
+/// * `tile_set` - As for `solve_rummikub_with_constraints`
+///
+/// # Returns
+/// JSON string with DrawOutlookResult
+#[wasm_bindgen]
+pub fn draw_outlook(
+    hand_tiles: &str,
+    table_melds: &str,
+    known_tiles: &str,
+    strategy: &str,
+    time_limit_ms: u64,
+    house_rules: &str,
+    tile_set: &str,
+) -> String {
+    let result =
+        draw_outlook_internal(hand_tiles, table_melds, known_tiles, strategy, time_limit_ms, house_rules, tile_set)
+        .unwrap_or_else(|e| DrawOutlookResult {
+            error: Some(e),
+            ..DrawOutlookResult::default()
+        });
+    serde_json::to_string(&result)
+        .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Serialization error: {}"}}"#, e))
+}
+
+/// Internal implementation of draw_outlook
+fn draw_outlook_internal(
+    hand_tiles: &str,
+    table_melds: &str,
+    known_tiles: &str,
+    strategy: &str,
+    time_limit_ms: u64,
+    house_rules: &str,
+    tile_set: &str,
+) -> Result<DrawOutlookResult, String> {
+    let hand = hand_from_json(hand_tiles)?;
+    let table = table_from_json(table_melds)?;
+    let known = hand_from_json(known_tiles)?;
+    let strategy = strategy_from_str(strategy)?;
+    let rules = rules_from_json(house_rules)?;
+    let tile_set = tile_set_from_str(tile_set)?;
+    tile_set.check(&hand, &table)?;
+
+    let unseen = tile_set.unseen(&hand, &table, &known);
+    let outlook = analysis::draw_outlook(&table, &hand, &unseen, time_limit_ms, strategy, &rules);
+
+    Ok(DrawOutlookResult {
+        success: true,
+        outcomes: outlook
+            .outcomes
+            .iter()
+            .map(|o| DrawOutcomeJson {
+                tile: o.tile.to_string(),
+                unseen: o.unseen,
+                can_play: o.can_play,
+                tiles_played: o.tiles_played,
+                goes_out: o.goes_out,
+            })
+            .collect(),
+        baseline_played: outlook.baseline_played,
+        useful_probability: outlook.useful_probability,
+        out_probability: outlook.out_probability,
+        search_completed: outlook.search_completed,
+        error: None,
+    })
+}
+
+/// JSON representation of a tile that completes a near-meld
+#[derive(Serialize, Deserialize)]
+pub struct CompletionJson {
+    pub tile: String,
+    pub unseen: u8,
+}
+
+/// JSON representation of a near-meld in the hand
+#[derive(Serialize, Deserialize)]
+pub struct NearMeldJson {
+    /// One of "pair", "two_tile_run", "gapped_run"
+    pub kind: String,
+    pub tiles: Vec<String>,
+    pub completing: Vec<CompletionJson>,
+    /// Unseen copies of all the completing tiles together
+    pub outs: u32,
+}
+
+/// Result of a hand structure analysis
+#[derive(Serialize, Deserialize)]
+pub struct HandStructureResult {
+    pub success: bool,
+    pub near_melds: Vec<NearMeldJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// List the near-melds in the hand (pairs, two-tile runs, gapped runs) with
+/// the tiles that would complete them and their unseen counts
+///
+/// # Arguments
+/// * `hand_tiles`, `table_melds`, `house_rules` - As for `solve_rummikub_with_rules`
+/// * `known_tiles` - JSON array of tiles known to be in other racks ("[]" if none)
+/// * `tile_set` - As for `solve_rummikub_with_constraints`
+///
+/// # Returns
+/// JSON string with HandStructureResult, near-melds with the most outs first
+#[wasm_bindgen]
+pub fn hand_structure(
+    hand_tiles: &str,
+    table_melds: &str,
+    known_tiles: &str,
+    house_rules: &str,
+    tile_set: &str,
+) -> String {
+    let result =
+        hand_structure_internal(hand_tiles, table_melds, known_tiles, house_rules, tile_set).unwrap_or_else(|e| {
+            HandStructureResult {
+                success: false,
+                near_melds: Vec::new(),
+                error: Some(e),
+            }
+        });
+    serde_json::to_string(&result)
+        .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Serialization error: {}"}}"#, e))
+}
+
+/// Internal implementation of hand_structure
+fn hand_structure_internal(
+    hand_tiles: &str,
+    table_melds: &str,
+    known_tiles: &str,
+    house_rules: &str,
+    tile_set: &str,
+) -> Result<HandStructureResult, String> {
+    let hand = hand_from_json(hand_tiles)?;
+    let table = table_from_json(table_melds)?;
+    let known = hand_from_json(known_tiles)?;
+    let rules = rules_from_json(house_rules)?;
+    let tile_set = tile_set_from_str(tile_set)?;
+    tile_set.check(&hand, &table)?;
+
+    let unseen = tile_set.unseen(&hand, &table, &known);
+    let near_melds = analysis::near_melds(&hand, &unseen, &rules)
+        .iter()
+        .map(|near| NearMeldJson {
+            kind: match near.kind {
+                analysis::NearMeldKind::Pair => "pair",
+                analysis::NearMeldKind::TwoTileRun => "two_tile_run",
+                analysis::NearMeldKind::GappedRun => "gapped_run",
+            }
+            .to_string(),
+            tiles: near.tiles.iter().map(|t| t.to_string()).collect(),
+            completing: near
+                .completing
+                .iter()
+                .map(|c| CompletionJson { tile: c.tile.to_string(), unseen: c.unseen })
+                .collect(),
+            outs: near.outs(),
+        })
+        .collect();
+
+    Ok(HandStructureResult {
+        success: true,
+        near_melds,
+        error: None,
+    })
+}
+
+/// JSON representation of whether one kind of hand tile can be played
+#[derive(Serialize, Deserialize)]
+pub struct TilePlayabilityJson {
+    pub tile: String,
+    pub copies: u8,
+    pub playable: bool,
+    /// Table meld indices the tile can be added to on its own
+    pub attaches_to: Vec<usize>,
+    pub needs_rearrangement: bool,
+}
+
+/// Result of a playability map query
+#[derive(Serialize, Deserialize, Default)]
+pub struct PlayabilityResult {
+    pub success: bool,
+    pub tiles: Vec<TilePlayabilityJson>,
+    pub search_completed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Report, for each tile kind in the hand, whether any legal play this turn
+/// uses it, which table melds it attaches to, and whether it needs a
+/// rearrangement
+///
+/// # Arguments
+/// * `hand_tiles`, `table_melds`, `house_rules` - As for `solve_rummikub_with_rules`
+/// * `time_limit_ms` - Total time for the analysis, shared between the tile kinds
+/// * `tile_set` - As for `solve_rummikub_with_constraints`
+///
+/// # Returns
+/// JSON string with PlayabilityResult
+#[wasm_bindgen]
+pub fn playability_map(
+    hand_tiles: &str,
+    table_melds: &str,
+    time_limit_ms: u64,
+    house_rules: &str,
+    tile_set: &str,
+) -> String {
+    let result =
+        playability_internal(hand_tiles, table_melds, time_limit_ms, house_rules, tile_set).unwrap_or_else(|e| {
+            PlayabilityResult {
+                error: Some(e),
+                ..PlayabilityResult::default()
+            }
+        });
+    serde_json::to_string(&result)
+        .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Serialization error: {}"}}"#, e))
+}
+
+/// Internal implementation of playability_map
+fn playability_internal(
+    hand_tiles: &str,
+    table_melds: &str,
+    time_limit_ms: u64,
+    house_rules: &str,
+    tile_set: &str,
+) -> Result<PlayabilityResult, String> {
+    let hand = hand_from_json(hand_tiles)?;
+    let table = table_from_json(table_melds)?;
+    let rules = rules_from_json(house_rules)?;
+    let tile_set = tile_set_from_str(tile_set)?;
+    tile_set.check(&hand, &table)?;
+
+    let map = analysis::playability_map(&table, &hand, time_limit_ms, &rules);
+
+    Ok(PlayabilityResult {
+        success: true,
+        tiles: map
+            .tiles
+            .iter()
+            .map(|t| TilePlayabilityJson {
+                tile: t.tile.to_string(),
+                copies: t.copies,
+                playable: t.playable,
+                attaches_to: t.attaches_to.clone(),
+                needs_rearrangement: t.needs_rearrangement,
+            })
+            .collect(),
+        search_completed: map.search_completed,
+        error: None,
+    })
+}
+
+/// JSON representation of the draw-or-play advisor settings.
+/// Missing fields take the advisor's defaults.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdvisorSettingsJson {
+    pub turns: usize,
+    pub samples: usize,
+    pub max_ms: u64,
+    pub seed: u64,
+}
+
+impl Default for AdvisorSettingsJson {
+    fn default() -> Self {
+        let defaults = analysis::AdvisorSettings::default();
+        AdvisorSettingsJson {
+            turns: defaults.turns,
+            samples: defaults.samples,
+            max_ms: defaults.max_ms,
+            seed: defaults.seed,
+        }
+    }
+}
+
+/// Result of the draw-or-play advisor
+#[derive(Serialize, Deserialize, Default)]
+pub struct TurnAdviceResult {
+    pub success: bool,
+    /// "play" or "draw"
+    pub recommendation: String,
+    /// The best play available now, as human-readable moves
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_play: Option<Vec<HumanMoveJson>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_remaining_if_play: Option<f64>,
+    pub expected_remaining_if_draw: f64,
+    /// Samples simulated for each choice before the time ran out
+    pub samples_completed: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Advise whether to lay down the best play now or draw instead
+///
+/// # Arguments
+/// * `hand_tiles`, `table_melds`, `strategy`, `house_rules` - As for `solve_rummikub_with_rules`
+/// * `known_tiles` - JSON array of tiles known to be in other racks ("[]" if none)
+/// * `settings` - JSON object with `turns` (at most 6), `samples` (at most 500), `max_ms` (time for
+///   the whole advice) and `seed` ("{}" for defaults)
+/// * `tile_set` - As for `solve_rummikub_with_constraints`
+///
+/// # Returns
+/// JSON string with TurnAdviceResult
+#[wasm_bindgen]
+pub fn advise_draw_or_play(
+    hand_tiles: &str,
+    table_melds: &str,
+    known_tiles: &str,
+    strategy: &str,
+    house_rules: &str,
+    settings: &str,
+    tile_set: &str,
+) -> String {
+    let result = advice_internal(hand_tiles, table_melds, known_tiles, strategy, house_rules, settings, tile_set)
+        .unwrap_or_else(|e| TurnAdviceResult {
+            error: Some(e),
+            ..TurnAdviceResult::default()
+        });
+    serde_json::to_string(&result)
+        .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Serialization error: {}"}}"#, e))
+}
+
+/// Internal implementation of advise_draw_or_play
+fn advice_internal(
+    hand_tiles: &str,
+    table_melds: &str,
+    known_tiles: &str,
+    strategy: &str,
+    house_rules: &str,
+    settings: &str,
+    tile_set: &str,
+) -> Result<TurnAdviceResult, String> {
+    let hand = hand_from_json(hand_tiles)?;
+    let table = table_from_json(table_melds)?;
+    let known = hand_from_json(known_tiles)?;
+    let strategy = strategy_from_str(strategy)?;
+    let rules = rules_from_json(house_rules)?;
+    let tile_set = tile_set_from_str(tile_set)?;
+    tile_set.check(&hand, &table)?;
+    let settings = advisor_settings_from_json(settings)?;
+
+    let unseen = tile_set.unseen(&hand, &table, &known);
+    let advice = analysis::advise_draw_or_play(&table, &hand, &unseen, strategy, &rules, &settings);
+
+    Ok(TurnAdviceResult {
+        success: true,
+        recommendation: turn_choice_to_json(advice.recommendation),
+        best_play: advice.best_play.as_ref().map(|moves| {
+            solver::translate_to_human_moves_checked(&table, &hand, moves, &rules)
+                .iter()
+                .map(human_move_to_json)
+                .collect()
+        }),
+        expected_remaining_if_play: advice.expected_remaining_if_play,
+        expected_remaining_if_draw: advice.expected_remaining_if_draw,
+        samples_completed: advice.samples_completed,
+        error: None,
     })
 }
 
+/// Result of the joker play-or-hold analysis
+#[derive(Serialize, Deserialize, Default)]
+pub struct JokerAdviceResult {
+    pub success: bool,
+    /// "play_joker" or "hold_joker"
+    pub recommendation: String,
+    /// The best play with the whole hand, as human-readable moves
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub with_joker: Option<Vec<HumanMoveJson>>,
+    /// The best play keeping the jokers, as human-readable moves
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub without_joker: Option<Vec<HumanMoveJson>>,
+    pub tile_difference: i32,
+    pub point_difference: i32,
+    pub expected_remaining_if_played: f64,
+    pub expected_remaining_if_held: f64,
+    /// Tiles fewer expected in hand by holding the jokers
+    pub hold_value: f64,
+    /// Samples simulated for each choice before the time ran out
+    pub samples_completed: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Compare the best play using the hand's jokers against the best play
+/// keeping them
+///
+/// # Arguments
+/// * `hand_tiles`, `table_melds`, `known_tiles`, `strategy`, `house_rules`,
+///   `settings` - As for `advise_draw_or_play`
+/// * `tile_set` - As for `solve_rummikub_with_constraints`
+///
+/// # Returns
+/// JSON string with JokerAdviceResult
+#[wasm_bindgen]
+pub fn advise_joker(
+    hand_tiles: &str,
+    table_melds: &str,
+    known_tiles: &str,
+    strategy: &str,
+    house_rules: &str,
+    settings: &str,
+    tile_set: &str,
+) -> String {
+    let result = joker_advice_internal(hand_tiles, table_melds, known_tiles, strategy, house_rules, settings, tile_set)
+        .unwrap_or_else(|e| JokerAdviceResult {
+            error: Some(e),
+            ..JokerAdviceResult::default()
+        });
+    serde_json::to_string(&result)
+        .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Serialization error: {}"}}"#, e))
+}
+
+/// Internal implementation of advise_joker
+fn joker_advice_internal(
+    hand_tiles: &str,
+    table_melds: &str,
+    known_tiles: &str,
+    strategy: &str,
+    house_rules: &str,
+    settings: &str,
+    tile_set: &str,
+) -> Result<JokerAdviceResult, String> {
+    let hand = hand_from_json(hand_tiles)?;
+    let table = table_from_json(table_melds)?;
+    let known = hand_from_json(known_tiles)?;
+    let strategy = strategy_from_str(strategy)?;
+    let rules = rules_from_json(house_rules)?;
+    let tile_set = tile_set_from_str(tile_set)?;
+    tile_set.check(&hand, &table)?;
+    let settings = advisor_settings_from_json(settings)?;
+
+    let unseen = tile_set.unseen(&hand, &table, &known);
+    let advice = analysis::advise_joker(&table, &hand, &unseen, strategy, &rules, &settings)?;
+    let human = |moves: &Vec<solver::SolverMove>| {
+        solver::translate_to_human_moves_checked(&table, &hand, moves, &rules)
+            .iter()
+            .map(human_move_to_json)
+            .collect()
+    };
+
+    Ok(JokerAdviceResult {
+        success: true,
+        recommendation: match advice.recommendation {
+            analysis::JokerChoice::PlayJoker => "play_joker",
+            analysis::JokerChoice::HoldJoker => "hold_joker",
+        }
+        .to_string(),
+        with_joker: advice.with_joker.as_ref().map(human),
+        without_joker: advice.without_joker.as_ref().map(human),
+        tile_difference: advice.tile_difference,
+        point_difference: advice.point_difference,
+        expected_remaining_if_played: advice.expected_remaining_if_played,
+        expected_remaining_if_held: advice.expected_remaining_if_held,
+        hold_value: advice.hold_value(),
+        samples_completed: advice.samples_completed,
+        error: None,
+    })
+}
+
+/// Result of a hint request; fields above the requested level are left out
+#[derive(Serialize, Deserialize, Default)]
+pub struct HintResult {
+    pub success: bool,
+    pub level: u8,
+    /// Hand tiles the best play lays down
+    pub tiles_played: usize,
+    /// Table melds the play touches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_melds: Option<Vec<MeldJson>>,
+    /// Hand tiles the play uses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hand_tiles: Option<Vec<String>>,
+    /// The full play as human-readable moves
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub human_moves: Option<Vec<HumanMoveJson>>,
+    pub search_completed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Give a graded hint toward the best play instead of the full answer
+///
+/// # Arguments
+/// * `hand_tiles`, `table_melds`, `strategy`, `time_limit_ms`, `house_rules` -
+///   As for `solve_rummikub_with_rules`
+/// * `level` - 1: tile count, 2: table melds to look at, 3: hand tiles to use,
+///   4: the full move list
+/// * `tile_set` - As for `solve_rummikub_with_constraints`
+///
+/// # Returns
+/// JSON string with HintResult
+#[wasm_bindgen]
+pub fn get_hint(
+    hand_tiles: &str,
+    table_melds: &str,
+    strategy: &str,
+    time_limit_ms: u64,
+    house_rules: &str,
+    level: u8,
+    tile_set: &str,
+) -> String {
+    let result = hint_internal(hand_tiles, table_melds, strategy, time_limit_ms, house_rules, level, tile_set)
+        .unwrap_or_else(|e| HintResult {
+            error: Some(e),
+            ..HintResult::default()
+        });
+    serde_json::to_string(&result)
+        .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Serialization error: {}"}}"#, e))
+}
+
+/// Internal implementation of get_hint
+fn hint_internal(
+    hand_tiles: &str,
+    table_melds: &str,
+    strategy: &str,
+    time_limit_ms: u64,
+    house_rules: &str,
+    level: u8,
+    tile_set: &str,
+) -> Result<HintResult, String> {
+    let hand = hand_from_json(hand_tiles)?;
+    let table = table_from_json(table_melds)?;
+    let strategy = strategy_from_str(strategy)?;
+    let rules = rules_from_json(house_rules)?;
+    let tile_set = tile_set_from_str(tile_set)?;
+    tile_set.check(&hand, &table)?;
+
+    let result =
+        solver::find_best_moves_with_rules(&mut table.clone(), &mut hand.clone(), time_limit_ms, strategy, &rules);
+    let hint = analysis::hint(&table, &hand, &result, level, &rules);
+
+    Ok(HintResult {
+        success: true,
+        level: hint.level,
+        tiles_played: hint.tiles_played,
+        table_melds: hint
+            .table_melds
+            .map(|indices| indices.iter().map(|&i| meld_to_json(&table.melds()[i])).collect()),
+        hand_tiles: hint.hand_tiles.map(|tiles| tiles.iter().map(|t| t.to_string()).collect()),
+        human_moves: hint.moves.map(|moves| moves.iter().map(human_move_to_json).collect()),
+        search_completed: result.search_completed,
+        error: None,
+    })
+}
+
+/// JSON representation of hypothetical changes to a position
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct WhatIfJson {
+    pub add_to_hand: Vec<String>,
+    pub remove_from_hand: Vec<String>,
+    pub remove_melds: Vec<usize>,
+    pub add_melds: Vec<MeldJson>,
+}
+
+/// JSON representation of the best play for one position
+#[derive(Serialize, Deserialize, Default)]
+pub struct OutcomeJson {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub human_moves: Option<Vec<HumanMoveJson>>,
+    /// Hand tiles the play lays down
+    pub played: Vec<String>,
+    /// Tiles left in hand afterwards
+    pub remaining: usize,
+    pub search_completed: bool,
+}
+
+/// Result of a what-if comparison
+#[derive(Serialize, Deserialize, Default)]
+pub struct WhatIfResult {
+    pub success: bool,
+    pub baseline: OutcomeJson,
+    /// Moves refer to the hypothetical table and hand
+    pub what_if: OutcomeJson,
+    pub played_difference: i32,
+    pub newly_played: Vec<String>,
+    pub no_longer_played: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Solve the position and a hypothetical variant of it, and compare them
+///
+/// # Arguments
+/// * `hand_tiles`, `table_melds`, `strategy`, `time_limit_ms`, `house_rules` -
+///   As for `solve_rummikub_with_rules`; the time is shared by both solves
+/// * `changes` - JSON object with `add_to_hand`, `remove_from_hand` (tile
+///   arrays), `remove_melds` (table meld indices) and `add_melds` (melds),
+///   e.g. {"add_to_hand": ["b9"]}
+/// * `tile_set` - As for `solve_rummikub_with_constraints`
+///
+/// # Returns
+/// JSON string with WhatIfResult
+#[wasm_bindgen]
+pub fn explore_what_if(
+    hand_tiles: &str,
+    table_melds: &str,
+    strategy: &str,
+    time_limit_ms: u64,
+    house_rules: &str,
+    changes: &str,
+    tile_set: &str,
+) -> String {
+    let result = what_if_internal(hand_tiles, table_melds, strategy, time_limit_ms, house_rules, changes, tile_set)
+        .unwrap_or_else(|e| WhatIfResult {
+            error: Some(e),
+            ..WhatIfResult::default()
+        });
+    serde_json::to_string(&result)
+        .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Serialization error: {}"}}"#, e))
+}
+
+/// Internal implementation of explore_what_if
+fn what_if_internal(
+    hand_tiles: &str,
+    table_melds: &str,
+    strategy: &str,
+    time_limit_ms: u64,
+    house_rules: &str,
+    changes: &str,
+    tile_set: &str,
+) -> Result<WhatIfResult, String> {
+    let hand = hand_from_json(hand_tiles)?;
+    let table = table_from_json(table_melds)?;
+    let strategy = strategy_from_str(strategy)?;
+    let rules = rules_from_json(house_rules)?;
+    let tile_set = tile_set_from_str(tile_set)?;
+    tile_set.check(&hand, &table)?;
+    let changes_json: WhatIfJson =
+        serde_json::from_str(changes).map_err(|e| format!("Invalid what-if JSON: {}", e))?;
+    let changes = analysis::WhatIf {
+        add_to_hand: changes_json.add_to_hand.iter().map(|t| Tile::from_string(t)).collect::<Result<_, _>>()?,
+        remove_from_hand: changes_json.remove_from_hand.iter().map(|t| Tile::from_string(t)).collect::<Result<_, _>>()?,
+        remove_melds: changes_json.remove_melds,
+        add_melds: changes_json.add_melds.into_iter().map(meld_from_json).collect::<Result<_, _>>()?,
+    };
+
+    let comparison = analysis::what_if(&table, &hand, &changes, time_limit_ms, strategy, &rules, &tile_set)?;
+    let outcome_json = |outcome: &analysis::Outcome, table: &Table, hand: &Hand| OutcomeJson {
+        human_moves: outcome.moves.as_ref().map(|moves| {
+            solver::translate_to_human_moves_checked(table, hand, moves, &rules)
+                .iter()
+                .map(human_move_to_json)
+                .collect()
+        }),
+        played: outcome.played.iter().map(|t| t.to_string()).collect(),
+        remaining: outcome.remaining,
+        search_completed: outcome.search_completed,
+    };
+    let strings = |tiles: &[Tile]| tiles.iter().map(|t| t.to_string()).collect();
+
+    Ok(WhatIfResult {
+        success: true,
+        baseline: outcome_json(&comparison.baseline, &table, &hand),
+        what_if: outcome_json(&comparison.what_if, &comparison.table, &comparison.hand),
+        played_difference: comparison.played_difference,
+        newly_played: strings(&comparison.newly_played),
+        no_longer_played: strings(&comparison.no_longer_played),
+        error: None,
+    })
+}
+
+/// JSON representation of one planned turn
+#[derive(Serialize, Deserialize)]
+pub struct PlannedTurnJson {
+    /// "play" or "draw"
+    pub choice: String,
+    /// Human-readable moves for the table as it stands at this turn
+    pub moves: Vec<HumanMoveJson>,
+}
+
+/// Result of the multi-turn planner
+#[derive(Serialize, Deserialize, Default)]
+pub struct TurnPlanResult {
+    pub success: bool,
+    pub turns: Vec<PlannedTurnJson>,
+    /// Tiles expected in hand after the plan, drawn tiles included
+    pub final_rack: usize,
+    /// The search finished within the time limit
+    pub search_completed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Plan the next turns to end with the smallest rack, treating draws as unknown tiles
+///
+/// # Arguments
+/// * `hand_tiles`, `table_melds`, `strategy`, `house_rules` - As for `solve_rummikub_with_rules`
+/// * `turns` - Number of own turns to plan, at most 6
+/// * `max_ms` - Time limit for the whole plan, shared between the solver runs
+/// * `tile_set` - As for `solve_rummikub_with_constraints`
+///
+/// # Returns
+/// JSON string with TurnPlanResult
+#[wasm_bindgen]
+pub fn plan_turns(
+    hand_tiles: &str,
+    table_melds: &str,
+    strategy: &str,
+    house_rules: &str,
+    turns: usize,
+    max_ms: u64,
+    tile_set: &str,
+) -> String {
+    let result = plan_internal(hand_tiles, table_melds, strategy, house_rules, turns, max_ms, tile_set)
+        .unwrap_or_else(|e| TurnPlanResult {
+            error: Some(e),
+            ..TurnPlanResult::default()
+        });
+    serde_json::to_string(&result)
+        .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Serialization error: {}"}}"#, e))
+}
+
+/// Internal implementation of plan_turns
+fn plan_internal(
+    hand_tiles: &str,
+    table_melds: &str,
+    strategy: &str,
+    house_rules: &str,
+    turns: usize,
+    max_ms: u64,
+    tile_set: &str,
+) -> Result<TurnPlanResult, String> {
+    let mut hand = hand_from_json(hand_tiles)?;
+    let mut table = table_from_json(table_melds)?;
+    let strategy = strategy_from_str(strategy)?;
+    let rules = rules_from_json(house_rules)?;
+    let tile_set = tile_set_from_str(tile_set)?;
+    tile_set.check(&hand, &table)?;
+    if turns > analysis::MAX_PLAN_TURNS {
+        return Err(format!("Plans cover at most {} turns, got {}", analysis::MAX_PLAN_TURNS, turns));
+    }
+
+    let plan = analysis::plan_turns(&table, &hand, turns, max_ms, strategy, &rules);
+
+    // Replay the plan to describe each turn against the table it is played on
+    let mut turns_json = Vec::with_capacity(plan.turns.len());
+    for turn in &plan.turns {
+        let moves = solver::translate_to_human_moves_checked(&table, &hand, &turn.moves, &rules);
+        turns_json.push(PlannedTurnJson {
+            choice: turn_choice_to_json(turn.choice),
+            moves: moves.iter().map(human_move_to_json).collect(),
+        });
+        solver::apply_moves(&mut table, &mut hand, &turn.moves);
+    }
+
+    Ok(TurnPlanResult {
+        success: true,
+        turns: turns_json,
+        final_rack: plan.final_rack,
+        search_completed: plan.search_completed,
+        error: None,
+    })
+}
+
+/// Result of an Okey hand analysis, with tiles named as the physical tiles
+/// in the hand (the okey tile itself is the wild, "w" is a false joker)
+#[derive(Serialize, Deserialize, Default)]
+pub struct OkeyResult {
+    pub success: bool,
+    /// The hand (after the discard, for 15 tiles) is a winning hand
+    pub wins: bool,
+    /// Suggested discard, for 15-tile hands
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discard: Option<String>,
+    /// Tiles left outside melds or pairs
+    pub unmatched: usize,
+    pub melds: Vec<Vec<String>>,
+    /// Set instead of `melds` when the hand is arranged as pairs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pairs: Option<Vec<[String; 2]>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Okey variant: check a 14-tile hand for a win, or pick the discard for a 15-tile hand
+///
+/// # Arguments
+/// * `hand_tiles` - JSON array of tile strings, printed jokers as "w" (e.g., ["r12", "r13", "r1", "w"])
+/// * `indicator` - The face-up indicator tile (e.g., "b9")
+///
+/// # Returns
+/// JSON string with OkeyResult
+#[wasm_bindgen]
+pub fn analyze_okey_hand(hand_tiles: &str, indicator: &str) -> String {
+    let result = okey_internal(hand_tiles, indicator).unwrap_or_else(|e| OkeyResult {
+        error: Some(e),
+        ..OkeyResult::default()
+    });
+    serde_json::to_string(&result)
+        .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Serialization error: {}"}}"#, e))
+}
+
+/// Internal implementation of analyze_okey_hand
+fn okey_internal(hand_tiles: &str, indicator: &str) -> Result<OkeyResult, String> {
+    let hand = hand_from_json(hand_tiles)?;
+    let rules = okey::OkeyRules::new(Tile::from_string(indicator)?)?;
+    TileSet::standard().check(&hand, &Table::new())?;
+    let size = hand.len();
+
+    let physical = |tile: &Tile| rules.physical_tile(*tile).to_string();
+    let arranged = |result: &mut OkeyResult, arrangement: &okey::OkeyArrangement| match arrangement {
+        okey::OkeyArrangement::Melds(melds) => {
+            result.melds = melds.iter().map(|m| m.tiles.iter().map(physical).collect()).collect();
+        }
+        okey::OkeyArrangement::Pairs(pairs) => {
+            result.pairs = Some(pairs.iter().map(|(a, b)| [physical(a), physical(b)]).collect());
+        }
+    };
+
+    if size == okey::WINNING_HAND_SIZE {
+        let (unmatched, arrangement) = okey::arrange_hand(&hand, &rules);
+        let mut result = OkeyResult {
+            success: true,
+            wins: unmatched == 0,
+            unmatched,
+            ..OkeyResult::default()
+        };
+        arranged(&mut result, &arrangement);
+        return Ok(result);
+    }
+
+    let discard = okey::best_discard(&hand, &rules)?;
+    let mut result = OkeyResult {
+        success: true,
+        wins: discard.wins(),
+        discard: Some(physical(&discard.discard)),
+        unmatched: discard.unmatched,
+        ..OkeyResult::default()
+    };
+    arranged(&mut result, &discard.arrangement);
+    Ok(result)
+}
+
+/// Name a turn choice for JSON output
+fn turn_choice_to_json(choice: analysis::TurnChoice) -> String {
+    match choice {
+        analysis::TurnChoice::Play => "play".to_string(),
+        analysis::TurnChoice::Draw => "draw".to_string(),
+    }
+}
+
+/// Convert a physical step to JSON
+fn tile_action_to_json(action: &solver::TileAction) -> TileActionJson {
+    match action {
+        solver::TileAction::Split { slot, after, new_slot } => TileActionJson::Split {
+            slot: *slot,
+            after: *after,
+            new_slot: *new_slot,
+        },
+        solver::TileAction::MoveTile { tile, from, to } => TileActionJson::MoveTile {
+            tile: tile.to_string(),
+            from: *from,
+            to: *to,
+        },
+        solver::TileAction::PlaceFromRack { tile, to } => TileActionJson::PlaceFromRack {
+            tile: tile.to_string(),
+            to: *to,
+        },
+        solver::TileAction::TakeToRack { tile, from } => TileActionJson::TakeToRack {
+            tile: tile.to_string(),
+            from: *from,
+        },
+    }
+}
+
+/// Convert a held-back tile to JSON
+fn held_tile_to_json(held: &solver::HeldTile) -> HeldTileJson {
+    let reason = match held.reason {
+        solver::HoldReason::ReserveMeld => "reserve_meld",
+        solver::HoldReason::Wild => "wild",
+        solver::HoldReason::Pair => "pair",
+        solver::HoldReason::NearRun => "near_run",
+    };
+    HeldTileJson { tile: held.tile.to_string(), reason: reason.to_string() }
+}
+
+/// Parse the advisor settings JSON, missing fields taking the defaults
+fn advisor_settings_from_json(settings: &str) -> Result<analysis::AdvisorSettings, String> {
+    let settings_json: AdvisorSettingsJson =
+        serde_json::from_str(settings).map_err(|e| format!("Invalid advisor settings JSON: {}", e))?;
+    if settings_json.turns > analysis::MAX_ADVISOR_TURNS {
+        return Err(format!(
+            "Advisor looks ahead at most {} turns, got {}",
+            analysis::MAX_ADVISOR_TURNS,
+            settings_json.turns
+        ));
+    }
+    if settings_json.samples > analysis::MAX_ADVISOR_SAMPLES {
+        return Err(format!(
+            "Advisor simulates at most {} samples, got {}",
+            analysis::MAX_ADVISOR_SAMPLES,
+            settings_json.samples
+        ));
+    }
+    Ok(analysis::AdvisorSettings {
+        turns: settings_json.turns,
+        samples: settings_json.samples,
+        max_ms: settings_json.max_ms,
+        seed: settings_json.seed,
+    })
+}
+
+/// JSON representation of the player's constraints
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ConstraintsJson {
+    pub locked: Vec<String>,
+    pub must_play: Vec<String>,
+    pub frozen_melds: Vec<usize>,
+}
+
+/// Parse the constraints JSON, checking that its tiles are in the hand and
+/// its melds on the table
+fn constraints_from_json(constraints: &str, hand: &Hand, table: &Table) -> Result<solver::PlayConstraints, String> {
+    let constraints_json: ConstraintsJson =
+        serde_json::from_str(constraints).map_err(|e| format!("Invalid constraints JSON: {}", e))?;
+
+    let locked = hand_from_strings(&constraints_json.locked)?;
+    let must_play = hand_from_strings(&constraints_json.must_play)?;
+    for (tile, _) in locked.iter().chain(must_play.iter()) {
+        if locked.count(tile) + must_play.count(tile) > hand.count(tile) {
+            return Err(format!("Constraints name {} more often than the hand holds it", tile));
+        }
+    }
+    if let Some(idx) = constraints_json.frozen_melds.iter().find(|&&idx| idx >= table.len()) {
+        return Err(format!("Frozen meld index {} is out of range", idx));
+    }
+
+    Ok(solver::PlayConstraints {
+        locked,
+        must_play,
+        frozen_melds: constraints_json.frozen_melds,
+    })
+}
+
+/// Parse a scoring strategy name
+fn strategy_from_str(strategy: &str) -> Result<solver::ScoringStrategy, String> {
+    match strategy {
+        "minimize_tiles" => Ok(solver::ScoringStrategy::MinimizeTiles),
+        "minimize_points" => Ok(solver::ScoringStrategy::MinimizePoints),
+        "keep_options" => Ok(solver::ScoringStrategy::KeepOptions),
+        _ => Err(format!("Unknown strategy: {}", strategy)),
+    }
+}
+
+/// Parse the house rules JSON into solver rules
+fn rules_from_json(house_rules: &str) -> Result<solver::HouseRules, String> {
+    let rules_json: HouseRulesJson =
+        serde_json::from_str(house_rules).map_err(|e| format!("Invalid house rules JSON: {}", e))?;
+    let rules = solver::HouseRules {
+        replay_with_hand_tile: rules_json.replay_with_hand_tile,
+        no_retrieval_from_groups_of_three: rules_json.no_retrieval_from_groups_of_three,
+        replacement_from_hand_only: rules_json.replacement_from_hand_only,
+        wrap_around_runs: rules_json.wrap_around_runs,
+    };
+    Ok(rules)
+}
+
+/// Parse a tile set JSON object ("{}" for the standard 106 tiles)
+fn tile_set_from_str(tile_set: &str) -> Result<TileSet, String> {
+    let tile_set_json: TileSetJson =
+        serde_json::from_str(tile_set).map_err(|e| format!("Invalid tile set JSON: {}", e))?;
+    Ok(tile_set_from_json(tile_set_json))
+}
+
+/// Parse a JSON array of tile strings into a Hand
+fn hand_from_json(hand_tiles: &str) -> Result<Hand, String> {
+    let hand_strs: Vec<String> =
+        serde_json::from_str(hand_tiles).map_err(|e| format!("Invalid hand JSON: {}", e))?;
+    hand_from_strings(&hand_strs)
+}
+
+/// Parse tile strings into a Hand
+fn hand_from_strings(tile_strs: &[String]) -> Result<Hand, String> {
+    let mut hand = Hand::new();
+    for tile_str in tile_strs {
+        hand.add(Tile::from_string(tile_str)?);
+    }
+    Ok(hand)
+}
+
+/// Parse a JSON array of meld objects into a Table
+fn table_from_json(table_melds: &str) -> Result<Table, String> {
+    let table_json: Vec<MeldJson> =
+        serde_json::from_str(table_melds).map_err(|e| format!("Invalid table JSON: {}", e))?;
+
+    let mut table = Table::new();
+    for meld_json in table_json {
+        table.add_meld(meld_from_json(meld_json)?);
+    }
+    Ok(table)
+}
+
 /// Convert JSON meld to internal Meld type
 fn meld_from_json(meld_json: MeldJson) -> Result<Meld, String> {
     let (meld_type, tile_strs) = match meld_json {
-        MeldJson::Group { tiles } => (MeldType::Group, tiles),
-        MeldJson::Run { tiles } => (MeldType::Run, tiles),
+        MeldJson::Group { tiles, .. } => (MeldType::Group, tiles),
+        MeldJson::Run { tiles, .. } => (MeldType::Run, tiles),
     };
 
     let mut tiles = VecDeque::new();
@@ -226,10 +1576,18 @@ fn meld_from_json(meld_json: MeldJson) -> Result<Meld, String> {
 /// Convert internal Meld to JSON representation
 fn meld_to_json(meld: &Meld) -> MeldJson {
     let tiles: Vec<String> = meld.tiles.iter().map(|t| t.to_string()).collect();
+    let jokers: Vec<JokerJson> = meld
+        .wild_assignments()
+        .iter()
+        .map(|a| JokerJson {
+            position: a.position,
+            represents: a.represents.to_string(),
+        })
+        .collect();
 
     match meld.meld_type {
-        MeldType::Group => MeldJson::Group { tiles },
-        MeldType::Run => MeldJson::Run { tiles },
+        MeldType::Group => MeldJson::Group { tiles, jokers },
+        MeldType::Run => MeldJson::Run { tiles, jokers },
     }
 }
 
@@ -290,6 +1648,30 @@ fn human_move_to_json(human_move: &solver::HumanMove) -> HumanMoveJson {
                 .collect(),
             result: meld_to_json(result),
         },
+        solver::HumanMove::MoveTile {
+            tile,
+            from,
+            remaining,
+            to,
+            added_tiles,
+            result,
+        } => HumanMoveJson::MoveTile {
+            tile: tile.to_string(),
+            from: meld_to_json(from),
+            remaining: meld_to_json(remaining),
+            to: to.as_ref().map(meld_to_json),
+            added_tiles: added_tiles.iter().map(|t| t.to_string()).collect(),
+            result: meld_to_json(result),
+        },
+        solver::HumanMove::InsertAndSplit {
+            original,
+            inserted,
+            parts,
+        } => HumanMoveJson::InsertAndSplit {
+            original: meld_to_json(original),
+            inserted: inserted.iter().map(|t| t.to_string()).collect(),
+            parts: parts.iter().map(meld_to_json).collect(),
+        },
         solver::HumanMove::Rearrange {
             consumed,
             produced,